@@ -1,4 +1,5 @@
 use scrypto::prelude::*;
+use sha2::{Digest, Sha256};
 
 /// The contents of a lock.
 #[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
@@ -7,6 +8,235 @@ pub enum LockContents {
     NonFungible(IndexSet<NonFungibleLocalId>),
 }
 
+/// The release schedule of a lock.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub enum LockSchedule {
+    /// Vests linearly from `start` to `end`, nothing is claimable before the optional `cliff`.
+    Linear {
+        start: Instant,
+        end: Instant,
+        cliff: Option<Instant>,
+    },
+    /// Vests in steps, each tranche releases its percentage of the total at its time.
+    Tranches {
+        tranches: Vec<(Instant, Decimal)>,
+    },
+    /// Vests non-fungibles in batches, each batch of ids is released at its time.
+    Batches {
+        batches: Vec<(Instant, IndexSet<NonFungibleLocalId>)>,
+    },
+}
+
+impl LockSchedule {
+    /// Returns the amount of `total` that has vested at `time`.
+    pub fn vested_amount(&self, total: Decimal, time: Instant) -> Decimal {
+        match self {
+            LockSchedule::Linear { start, end, cliff } => {
+                if time <= *start || cliff.is_some_and(|cliff| time < cliff) {
+                    Decimal::ZERO
+                } else if time >= *end {
+                    total
+                } else {
+                    let elapsed = time.seconds_since_unix_epoch - start.seconds_since_unix_epoch;
+                    let duration = end.seconds_since_unix_epoch - start.seconds_since_unix_epoch;
+                    total * Decimal::from(elapsed) / Decimal::from(duration)
+                }
+            }
+            LockSchedule::Tranches { tranches } => {
+                let percentage = tranches.iter()
+                    .filter(|(tranche_time, _)| time >= *tranche_time)
+                    .fold(Decimal::ZERO, |sum, (_, percentage)| sum + *percentage);
+                if percentage == dec!(100) {
+                    total
+                } else {
+                    total * percentage / dec!(100)
+                }
+            }
+            LockSchedule::Batches { .. } => Decimal::from(self.released_ids(time).len() as u64),
+        }
+    }
+
+    /// Returns the non-fungible ids that have been released at `time`, empty for fungible schedules.
+    pub fn released_ids(&self, time: Instant) -> IndexSet<NonFungibleLocalId> {
+        match self {
+            LockSchedule::Batches { batches } => batches.iter()
+                .filter(|(batch_time, _)| time >= *batch_time)
+                .flat_map(|(_, ids)| ids.iter().cloned())
+                .collect(),
+            _ => IndexSet::new(),
+        }
+    }
+
+    /// Returns the number of tranches or batches that have been released at `time`, zero for linear schedules.
+    pub fn released_tranches(&self, time: Instant) -> u64 {
+        match self {
+            LockSchedule::Linear { .. } => 0,
+            LockSchedule::Tranches { tranches } => {
+                tranches.iter().filter(|(tranche_time, _)| time >= *tranche_time).count() as u64
+            }
+            LockSchedule::Batches { batches } => {
+                batches.iter().filter(|(batch_time, _)| time >= *batch_time).count() as u64
+            }
+        }
+    }
+}
+
+/// How a lock can be unlocked.
+#[derive(ScryptoSbor, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum LockKind {
+    /// Unlockable once the unlock time has passed.
+    Timed,
+    /// Never unlockable, the lock can no longer be modified.
+    Permanent,
+    /// Unlockable once a condition is met, such as an early unlock rule being satisfied.
+    Conditional,
+}
+
+impl LockKind {
+    /// Returns the kind of a lock with the given unlock time and options.
+    pub fn of(unlockable_at: Option<Instant>, options: &LockOptions) -> Self {
+        if options.early_unlock_rule.is_some()
+            || options.hash_lock.is_some()
+            || options.price_condition.is_some()
+            || options.unlock_condition.is_some()
+        {
+            LockKind::Conditional
+        } else if unlockable_at.is_some() {
+            LockKind::Timed
+        } else {
+            LockKind::Permanent
+        }
+    }
+}
+
+/// A hash commitment that releases a lock to a recipient when its preimage is revealed.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub struct HashLock {
+    /// The SHA-256 hash of the secret preimage, the same commitment used by HTLCs on other ledgers.
+    pub hash: Hash,
+    /// The account that receives the items when the preimage is revealed.
+    pub recipient: Global<Account>,
+}
+
+impl HashLock {
+    /// Returns the SHA-256 hash of a preimage.
+    pub fn hash_of(preimage: &[u8]) -> Hash {
+        Hash(Sha256::digest(preimage).into())
+    }
+}
+
+/// A price target that makes a lock unlockable once an oracle reports it has been reached.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub struct PriceCondition {
+    /// The oracle component, providing a `get_price(resource) -> Decimal` method.
+    pub oracle: ComponentAddress,
+    /// The resource whose price is checked.
+    pub resource: ResourceAddress,
+    /// The price at or above which the lock is unlockable.
+    pub threshold: Decimal,
+}
+
+impl PriceCondition {
+    /// Returns whether the oracle price of the resource has reached the threshold.
+    pub fn is_met(&self) -> bool {
+        let price: Decimal = Global::<AnyComponent>::from(self.oracle).call("get_price", &(self.resource,));
+        price >= self.threshold
+    }
+}
+
+/// How an unlock condition is combined with the unlock time.
+#[derive(ScryptoSbor, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ConditionMode {
+    /// Unlockable once the unlock time has passed and the condition is met.
+    And,
+    /// Unlockable once the unlock time has passed or the condition is met.
+    Or,
+}
+
+/// An external condition that a lock depends on to be unlocked.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub struct UnlockCondition {
+    /// The component evaluating the condition.
+    pub component: ComponentAddress,
+    /// The name of a method of the component without arguments, returning whether the condition is met.
+    pub method: String,
+    /// How the condition is combined with the unlock time.
+    pub mode: ConditionMode,
+}
+
+impl UnlockCondition {
+    /// Returns whether the component reports the condition as met.
+    pub fn is_met(&self) -> bool {
+        Global::<AnyComponent>::from(self.component).call_raw(&self.method, scrypto_args!())
+    }
+}
+
+/// The name, description and key image shown on a lock receipt.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub struct LockDisplay {
+    pub name: String,
+    pub description: String,
+    pub key_image_url: Url,
+}
+
+/// Optional settings of a lock, fixed at lock time.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug, Default)]
+pub struct LockOptions {
+    /// The account that anyone can release the items to once they are unlockable.
+    pub beneficiary: Option<Global<Account>>,
+    /// The access rule that, when satisfied, allows unlocking before the unlock time.
+    pub early_unlock_rule: Option<AccessRule>,
+    /// Whether the lock receipt is minted as a non-transferable soulbound receipt.
+    pub soulbound: bool,
+    /// The access rule of the grantor that can revoke the unvested part of a vesting lock.
+    pub revoke_rule: Option<AccessRule>,
+    /// The hash commitment that releases the items to a recipient before the unlock time, which acts as timeout.
+    pub hash_lock: Option<HashLock>,
+    /// The price target that, when reached, allows unlocking before the unlock time.
+    pub price_condition: Option<PriceCondition>,
+    /// The external condition that, combined with the unlock time, decides when the lock is unlockable.
+    pub unlock_condition: Option<UnlockCondition>,
+    /// The access rule, such as an M of N badge threshold, that must also be satisfied to unlock.
+    pub unlock_rule: Option<AccessRule>,
+}
+
+/// The highest basis point fee that can be set, so a fee change can never take more than this cut of a lock.
+pub const MAX_FEE_BASIS_POINTS: u16 = 500;
+
+/// The fee charged when creating a lock or adding to one.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub enum LockFee {
+    /// A flat amount of a resource, paid with a separate fee payment.
+    Flat {
+        resource: ResourceAddress,
+        amount: Decimal,
+    },
+    /// A cut of each fungible item locked, in basis points.
+    BasisPoints(u16),
+}
+
+/// The policy deciding which resources can be locked.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub enum ResourcePolicy {
+    /// Any resource can be locked.
+    AllowAll,
+    /// Only the listed resources can be locked.
+    Allowlist(IndexSet<ResourceAddress>),
+    /// Any resource except the listed resources can be locked.
+    Denylist(IndexSet<ResourceAddress>),
+}
+
+impl ResourcePolicy {
+    /// Returns whether the policy allows locking a resource.
+    pub fn allows(&self, resource: &ResourceAddress) -> bool {
+        match self {
+            ResourcePolicy::AllowAll => true,
+            ResourcePolicy::Allowlist(resources) => resources.contains(resource),
+            ResourcePolicy::Denylist(resources) => !resources.contains(resource),
+        }
+    }
+}
+
 /// Non-fungible data for a lock receipt.
 #[derive(ScryptoSbor, NonFungibleData, Clone, Eq, PartialEq, Debug)]
 pub struct LockReceipt {
@@ -14,20 +244,82 @@ pub struct LockReceipt {
     pub description: String,
     pub key_image_url: Url,
     pub resource: ResourceAddress,
-    pub locked_contents: LockContents,
+    #[mutable] pub kind: LockKind,
+    #[mutable] pub locked_contents: LockContents,
+    pub additional_contents: IndexMap<ResourceAddress, LockContents>,
     pub locked_at: Instant,
-    pub unlockable_at: Option<Instant>,
+    #[mutable] pub unlockable_at: Option<Instant>,
+    pub schedule: Option<LockSchedule>,
+    pub options: LockOptions,
+    #[mutable] pub claimed_amount: Decimal,
+    #[mutable] pub claimed_tranches: u64,
     #[mutable] pub unlocked_at: Option<Instant>,
+    #[mutable] pub revoked_at: Option<Instant>,
+}
+
+/// The current state of a lock.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug)]
+pub struct LockInfo {
+    pub lock_id: NonFungibleLocalId,
+    pub lock_receipt: LockReceipt,
+    pub balance: Decimal,
+    pub additional_balances: IndexMap<ResourceAddress, Decimal>,
+}
+
+/// The position of a lock in the index of locks of a resource.
+#[derive(ScryptoSbor, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct LockIndexKey {
+    pub resource: ResourceAddress,
+    pub index: u64,
+}
+
+/// Aggregate statistics of the locks of a resource.
+#[derive(ScryptoSbor, Clone, Eq, PartialEq, Debug, Default)]
+pub struct ResourceLockStats {
+    /// The amount locked, if the resource is fungible.
+    pub locked_amount: Decimal,
+    /// The number of non-fungibles locked, if the resource is non-fungible.
+    pub locked_non_fungibles: u64,
+    /// The number of locks that have not been unlocked.
+    pub active_locks: u64,
+}
+
+impl ResourceLockStats {
+    /// Returns the stats as metadata-friendly strings.
+    pub fn to_metadata(&self) -> IndexMap<String, String> {
+        indexmap!(
+            "locked_amount".to_string() => self.locked_amount.to_string(),
+            "locked_non_fungibles".to_string() => self.locked_non_fungibles.to_string(),
+            "active_locks".to_string() => self.active_locks.to_string(),
+        )
+    }
+
+    fn add(&mut self, contents: &LockContents) {
+        match contents {
+            LockContents::Fungible(amount) => self.locked_amount += *amount,
+            LockContents::NonFungible(ids) => self.locked_non_fungibles += ids.len() as u64,
+        }
+    }
+
+    fn remove(&mut self, contents: &LockContents) {
+        match contents {
+            LockContents::Fungible(amount) => self.locked_amount -= *amount,
+            LockContents::NonFungible(ids) => self.locked_non_fungibles -= ids.len() as u64,
+        }
+    }
 }
 
 /// Event emitted when an item is locked.
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
 pub struct EventLock {
     pub lock_id: NonFungibleLocalId,
+    pub kind: LockKind,
     pub resource: ResourceAddress,
     pub locked_contents: LockContents,
     pub locked_at: Instant,
     pub unlockable_at: Option<Instant>,
+    pub schedule: Option<LockSchedule>,
+    pub options: LockOptions,
 }
 
 /// Event emitted when an item is unlocked.
@@ -41,21 +333,224 @@ pub struct EventUnlock {
     pub unlocked_at: Instant,
 }
 
+/// Event emitted when multiple resources are locked together.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventLockMany {
+    pub lock_id: NonFungibleLocalId,
+    pub kind: LockKind,
+    pub locked_contents: IndexMap<ResourceAddress, LockContents>,
+    pub locked_at: Instant,
+    pub unlockable_at: Option<Instant>,
+    pub options: LockOptions,
+}
+
+/// Event emitted when multiple resources locked together are unlocked.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventUnlockMany {
+    pub lock_id: NonFungibleLocalId,
+    pub locked_contents: IndexMap<ResourceAddress, LockContents>,
+    pub locked_at: Instant,
+    pub unlockable_at: Option<Instant>,
+    pub unlocked_at: Instant,
+}
+
+/// Event emitted when part of a fungible item is unlocked.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventPartialUnlock {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub amount: Decimal,
+    pub locked_contents: LockContents,
+    pub unlocked_at: Instant,
+}
+
+/// Event emitted when an item is added to an existing lock.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventAddToLock {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub added_contents: LockContents,
+    pub locked_contents: LockContents,
+    pub added_at: Instant,
+}
+
+/// Event emitted when a lock is extended.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventExtend {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub old_unlockable_at: Option<Instant>,
+    pub new_unlockable_at: Option<Instant>,
+    pub extended_at: Instant,
+}
+
+/// Event emitted when a lock is split into multiple locks.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventSplit {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub split_lock_ids: Vec<NonFungibleLocalId>,
+    pub split_at: Instant,
+}
+
+/// Event emitted when multiple locks are merged into one lock.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventMerge {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub merged_lock_ids: Vec<NonFungibleLocalId>,
+    pub merged_at: Instant,
+}
+
+/// Event emitted when the lock fee is changed.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventFeeChange {
+    pub old_fee: Option<LockFee>,
+    pub new_fee: Option<LockFee>,
+    pub changed_at: Instant,
+}
+
+/// Event emitted when the resource policy is changed.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventResourcePolicyChange {
+    pub policy: ResourcePolicy,
+    pub changed_at: Instant,
+}
+
+/// Event emitted when the locker is paused.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventPause {
+    pub paused_at: Instant,
+}
+
+/// Event emitted when the locker is unpaused.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventUnpause {
+    pub unpaused_at: Instant,
+}
+
+/// Event emitted when the unvested part of a vesting lock is revoked.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventRevoke {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub revoked_contents: LockContents,
+    pub vested_contents: LockContents,
+    pub revoked_at: Instant,
+}
+
+/// Event emitted when a hash lock is claimed by revealing its preimage.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventClaimWithPreimage {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub preimage: Vec<u8>,
+    pub recipient: Global<Account>,
+    pub claimed_at: Instant,
+}
+
+/// Event emitted when a hash lock is refunded after its timeout.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventRefund {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub refunded_at: Instant,
+}
+
+/// Event emitted when vested tokens are claimed.
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Eq, PartialEq, Debug)]
+pub struct EventClaimVested {
+    pub lock_id: NonFungibleLocalId,
+    pub resource: ResourceAddress,
+    pub amount: Decimal,
+    pub claimed_amount: Decimal,
+    pub claimed_at: Instant,
+}
+
 #[blueprint]
 #[types(
     NonFungibleLocalId,
     Vault,
+    IndexMap<ResourceAddress, Vault>,
+    ResourceAddress,
+    LockIndexKey,
+    u64,
+    ResourceLockStats,
 )]
 #[events(
     EventLock,
     EventUnlock,
+    EventLockMany,
+    EventUnlockMany,
+    EventPartialUnlock,
+    EventAddToLock,
+    EventExtend,
+    EventSplit,
+    EventMerge,
+    EventClaimVested,
+    EventFeeChange,
+    EventResourcePolicyChange,
+    EventPause,
+    EventUnpause,
+    EventRevoke,
+    EventClaimWithPreimage,
+    EventRefund,
 )]
 mod locker_mod {
+    enable_method_auth! {
+        roles {
+            fee_manager => updatable_by: [OWNER];
+            pauser => updatable_by: [OWNER];
+        },
+        methods {
+            lock => PUBLIC;
+            lock_many => PUBLIC;
+            lock_vested => PUBLIC;
+            claim_vested => PUBLIC;
+            revoke => PUBLIC;
+            add_to_lock => PUBLIC;
+            extend_lock => PUBLIC;
+            partial_unlock => PUBLIC;
+            unlock => PUBLIC;
+            unlock_with_proof => PUBLIC;
+            unlock_to_beneficiary => PUBLIC;
+            claim_with_preimage => PUBLIC;
+            refund => PUBLIC;
+            split => PUBLIC;
+            merge => PUBLIC;
+            get_lock => PUBLIC;
+            get_locks_by_resource => PUBLIC;
+            get_lock_count_by_resource => PUBLIC;
+            total_locked => PUBLIC;
+            get_resource_lock_stats => PUBLIC;
+            get_resource_lock_stats_metadata => PUBLIC;
+            get_fee => PUBLIC;
+            set_fee => restrict_to: [fee_manager];
+            withdraw_fees => restrict_to: [fee_manager];
+            get_resource_policy => PUBLIC;
+            set_resource_policy => restrict_to: [OWNER];
+            set_resource_listed => restrict_to: [OWNER];
+            is_paused => PUBLIC;
+            pause => restrict_to: [pauser];
+            unpause => restrict_to: [pauser];
+        }
+    }
+
     struct Locker {
         lock_receipt_manager: ResourceManager,
+        soulbound_lock_receipt_manager: ResourceManager,
         counter: u64,
         vaults: KeyValueStore<NonFungibleLocalId, Vault>,
+        additional_vaults: KeyValueStore<NonFungibleLocalId, IndexMap<ResourceAddress, Vault>>,
+        locks_by_resource: KeyValueStore<LockIndexKey, NonFungibleLocalId>,
+        lock_counts_by_resource: KeyValueStore<ResourceAddress, u64>,
+        resource_lock_stats: KeyValueStore<ResourceAddress, ResourceLockStats>,
         used_lock_receipts: Vault,
+        used_soulbound_lock_receipts: Vault,
+        fee: Option<LockFee>,
+        fee_vaults: KeyValueStore<ResourceAddress, Vault>,
+        resource_policy: ResourcePolicy,
+        paused: bool,
     }
 
     impl Locker {
@@ -63,7 +558,9 @@ mod locker_mod {
         /// 
         /// # Arguments
         /// 
-        /// * `owner_resource` - The owner role resource used to update metadata.
+        /// * `owner_resource` - The owner role resource used to update metadata, roles and the resource policy.
+        /// * `fee_manager_resource` - The fee manager role resource used to set and withdraw fees.
+        /// * `pauser_resource` - The pauser role resource used to pause and unpause the locker.
         /// 
         /// # Returns
         /// 
@@ -71,6 +568,8 @@ mod locker_mod {
         /// 
         pub fn new(
             owner_resource: ResourceAddress,
+            fee_manager_resource: ResourceAddress,
+            pauser_resource: ResourceAddress,
             info_url: Url,
             icon_url: Url,
         ) -> Global<Locker> {
@@ -94,6 +593,35 @@ mod locker_mod {
                     init {
                         "name" => "LockReceipt", updatable;
                         "description" => "Don't trust, verify.", updatable;
+                        "info_url" => info_url.clone(), updatable;
+                        "icon_url" => icon_url.clone(), updatable;
+                        "locker" => this, locked;
+                    }
+                })
+                .create_with_no_initial_supply();
+
+            // Create the soulbound lock receipt manager, its receipts can not be withdrawn once deposited
+            let soulbound_lock_receipt_manager = ResourceBuilder::new_integer_non_fungible::<LockReceipt>(owner_role.clone())
+                .mint_roles(mint_roles! {
+                    minter => rule!(require(global_caller(this)));
+                    minter_updater => rule!(deny_all);
+                })
+                .non_fungible_data_update_roles(non_fungible_data_update_roles! {
+                    non_fungible_data_updater => rule!(require(global_caller(this)));
+                    non_fungible_data_updater_updater => rule!(deny_all);
+                })
+                .withdraw_roles(withdraw_roles! {
+                    withdrawer => rule!(deny_all);
+                    withdrawer_updater => rule!(deny_all);
+                })
+                .deposit_roles(deposit_roles! {
+                    depositor => rule!(allow_all);
+                    depositor_updater => rule!(deny_all);
+                })
+                .metadata(metadata! {
+                    init {
+                        "name" => "Soulbound LockReceipt", updatable;
+                        "description" => "Don't trust, verify.", updatable;
                         "info_url" => info_url, updatable;
                         "icon_url" => icon_url, updatable;
                         "locker" => this, locked;
@@ -104,22 +632,69 @@ mod locker_mod {
             // Instantiate the locker
             Self {
                 lock_receipt_manager,
+                soulbound_lock_receipt_manager,
                 counter: 0,
                 vaults: KeyValueStore::new_with_registered_type(),
+                additional_vaults: KeyValueStore::new_with_registered_type(),
+                locks_by_resource: KeyValueStore::new_with_registered_type(),
+                lock_counts_by_resource: KeyValueStore::new_with_registered_type(),
+                resource_lock_stats: KeyValueStore::new_with_registered_type(),
                 used_lock_receipts: Vault::new(lock_receipt_manager.address()),
+                used_soulbound_lock_receipts: Vault::new(soulbound_lock_receipt_manager.address()),
+                fee: None,
+                fee_vaults: KeyValueStore::new_with_registered_type(),
+                resource_policy: ResourcePolicy::AllowAll,
+                paused: false,
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
+            .roles(roles! {
+                fee_manager => rule!(require(fee_manager_resource));
+                pauser => rule!(require(pauser_resource));
+            })
             .with_address(address_reservation)
             .globalize()
         }
 
+        /// Creates a new locker owned by a newly minted owner badge.
+        /// 
+        /// The owner badge also holds the fee manager and pauser roles.
+        /// 
+        /// # Returns
+        /// 
+        /// A new locker and a bucket containing its owner badge.
+        /// 
+        pub fn new_with_owner_badge(
+            info_url: Url,
+            icon_url: Url,
+        ) -> (Global<Locker>, Bucket) {
+            // Mint the owner badge with a fixed supply
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata! {
+                    init {
+                        "name" => "Locker Owner Badge", locked;
+                        "icon_url" => icon_url.clone(), locked;
+                    }
+                })
+                .mint_initial_supply(1);
+            let owner_resource = owner_badge.resource_address();
+
+            // Instantiate the locker
+            let locker = Self::new(owner_resource, owner_resource, owner_resource, info_url, icon_url);
+
+            (locker, owner_badge.into())
+        }
+
         /// Locks an item and returns a lock receipt.
         /// 
         /// # Arguments
         /// 
         /// * `item` - The item to lock.
         /// * `unlockable_at` - The time at which the item can be unlocked.
+        /// * `display` - The name, description and key image of the lock receipt.
+        /// * `options` - The optional settings of the lock.
+        /// * `fee_payment` - The payment of a flat lock fee, if any.
         /// 
         /// # Returns
         /// 
@@ -129,118 +704,1699 @@ mod locker_mod {
         /// 
         /// * `EventLock` - An event emitted when a item is locked.
         /// 
+        /// # Panics
+        /// 
+        /// * If the locker is paused.
+        /// * If the resource policy does not allow the item.
+        /// * If the fee payment does not match the lock fee.
+        /// 
         pub fn lock(
             &mut self,
             item: Bucket,
             unlockable_at: Option<Instant>,
-            name: String,
-            description: String,
-            key_image_url: Url,
+            display: LockDisplay,
+            options: LockOptions,
+            fee_payment: Option<Bucket>,
         ) -> Bucket {
-            // Get the current time
-            let current_time = Clock::current_time(TimePrecisionV2::Second);
-
-            // Get the next id and increment the counter
-            let id = NonFungibleLocalId::integer(self.counter);
-            self.counter += 1;
-            
-            // Get the resource address and the bucket contents
-            let resource = item.resource_address();
-            let locked_contents = match item.resource_manager().resource_type() {
-                ResourceType::Fungible{..} => LockContents::Fungible(item.amount()),
-                ResourceType::NonFungible{..} => LockContents::NonFungible(item.as_non_fungible().non_fungible_local_ids()),
-            };
-
-            // Deposit the item into the vault
-            self.vaults.insert(id.clone(), Vault::with_bucket(item));
-
-            // Emit new lock event
-            Runtime::emit_event(EventLock {
-                lock_id: id.clone(),
-                resource,
-                locked_contents: locked_contents.clone(),
-                locked_at: Clock::current_time(TimePrecisionV2::Second),
-                unlockable_at,
-            });
+            // Assert the locker is not paused, the item is allowed and collect the lock fee
+            let mut items = vec![item];
+            self.assert_not_paused();
+            self.assert_allowed(&items);
+            self.collect_fee(&mut items, fee_payment);
 
-            // Mint and return the lock receipt
-            self.lock_receipt_manager.mint_non_fungible(&id, LockReceipt {
-                name,
-                description,
-                key_image_url,
-                resource,
-                locked_contents,
-                locked_at: current_time,
-                unlockable_at,
-                unlocked_at: None,
-            })
+            self.create_lock(items, unlockable_at, None, display, options)
         }
-        
-        /// Takes one or more lock receipts and returns the items they unlock.
+
+        /// Locks multiple items of different resources together and returns a single lock receipt.
         /// 
         /// # Arguments
         /// 
-        /// * `lock_receipts` - The lock receipts to unlock.
+        /// * `items` - The items to lock, each of a different resource.
+        /// * `unlockable_at` - The time at which the items can be unlocked.
+        /// * `display` - The name, description and key image of the lock receipt.
+        /// * `options` - The optional settings of the lock.
+        /// * `fee_payment` - The payment of a flat lock fee, if any.
         /// 
         /// # Returns
         /// 
-        /// A vector of buckets, each containing the item that was unlocked.
+        /// A bucket containing the lock receipt.
         /// 
         /// # Emits
         /// 
-        /// * `EventUnlock` - An event emitted when an item is unlocked.
+        /// * `EventLockMany` - An event emitted when multiple items are locked.
         /// 
         /// # Panics
         /// 
-        /// * If the lock receipts are invalid.
-        /// * If any lock is not unlockable.
+        /// * If there are no items.
+        /// * If any two items are of the same resource.
+        /// * If the locker is paused.
+        /// * If the resource policy does not allow any item.
+        /// * If the fee payment does not match the lock fee.
         /// 
-        pub fn unlock(&mut self, lock_receipts: Bucket) -> Vec<Bucket> {
-            // Assert valid lock receipts
-            assert!(
-                lock_receipts.resource_address() == self.lock_receipt_manager.address(),
-                "Invalid lock receipts"
-            );
-
-            // Get the current time
-            let current_time = Clock::current_time(TimePrecisionV2::Second);
-
-            // Check each lock receipt
-            let mut items = Vec::new();
-            for lock_receipt in lock_receipts.as_non_fungible().non_fungibles::<LockReceipt>() {
-                // Get the id and the lock receipt data
-                let id = lock_receipt.local_id();
-                let lock_receipt_data = lock_receipt.data();
-
-                // Assert item is unlockable
-                assert!(
-                    lock_receipt_data.unlockable_at.is_some() && current_time >= lock_receipt_data.unlockable_at.unwrap(),
-                    "Item can not yet be unlocked, unlockable at: {:?}", lock_receipt_data.unlockable_at
-                );
+        pub fn lock_many(
+            &mut self,
+            mut items: Vec<Bucket>,
+            unlockable_at: Option<Instant>,
+            display: LockDisplay,
+            options: LockOptions,
+            fee_payment: Option<Bucket>,
+        ) -> Bucket {
+            // Assert there are items of distinct resources
+            assert!(!items.is_empty(), "No items to lock");
+            let resources: IndexSet<ResourceAddress> = items.iter().map(|item| item.resource_address()).collect();
+            assert!(resources.len() == items.len(), "Items must be of different resources");
 
-                // Take the item from the vault
-                let item = self.vaults.get_mut(id).unwrap().take_all();                
-                items.push(item);
+            // Assert the locker is not paused, the items are allowed and collect the lock fee
+            self.assert_not_paused();
+            self.assert_allowed(&items);
+            self.collect_fee(&mut items, fee_payment);
 
-                // Update the lock receipt
-                self.lock_receipt_manager.update_non_fungible_data(&id, "unlocked_at", Some(current_time));
+            self.create_lock(items, unlockable_at, None, display, options)
+        }
 
-                // Emit unlock event
-                Runtime::emit_event(EventUnlock {
+        /// Locks an item that vests over time and returns a lock receipt.
+        /// 
+        /// Fungible items vest linearly or in tranches, non-fungible items vest in batches of ids.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `item` - The item to lock.
+        /// * `schedule` - The vesting schedule, the item is fully unlockable at its end.
+        /// * `display` - The name, description and key image of the lock receipt.
+        /// * `options` - The optional settings of the lock.
+        /// * `fee_payment` - The payment of a flat lock fee, if any.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the lock receipt.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventLock` - An event emitted when a item is locked.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the item does not match the kind of schedule.
+        /// * If the schedule is invalid.
+        /// * If the options include a hash lock.
+        /// * If the locker is paused.
+        /// * If the resource policy does not allow the item.
+        /// * If the fee payment does not match the lock fee.
+        /// 
+        pub fn lock_vested(
+            &mut self,
+            item: Bucket,
+            schedule: LockSchedule,
+            display: LockDisplay,
+            options: LockOptions,
+            fee_payment: Option<Bucket>,
+        ) -> Bucket {
+            // Assert the schedule is valid for the item
+            let is_fungible = item.resource_manager().resource_type().is_fungible();
+            let unlockable_at = match &schedule {
+                LockSchedule::Linear { start, end, cliff } => {
+                    assert!(is_fungible, "Vesting locks require a fungible item");
+                    assert!(start < end, "Vesting must end after it starts");
+                    assert!(
+                        match cliff {
+                            Some(cliff) => start <= cliff && cliff <= end,
+                            None => true,
+                        },
+                        "Vesting cliff must be between start and end"
+                    );
+                    *end
+                }
+                LockSchedule::Tranches { tranches } => {
+                    assert!(is_fungible, "Vesting locks require a fungible item");
+                    assert!(!tranches.is_empty(), "Vesting requires at least one tranche");
+                    assert!(
+                        tranches.windows(2).all(|pair| pair[0].0 < pair[1].0),
+                        "Vesting tranches must be in chronological order"
+                    );
+                    assert!(
+                        tranches.iter().all(|(_, percentage)| *percentage > Decimal::ZERO),
+                        "Vesting tranche percentages must be positive"
+                    );
+                    assert!(
+                        tranches.iter().fold(Decimal::ZERO, |sum, (_, percentage)| sum + *percentage) == dec!(100),
+                        "Vesting tranche percentages must sum to 100"
+                    );
+                    tranches.last().unwrap().0
+                }
+                LockSchedule::Batches { batches } => {
+                    assert!(!is_fungible, "Batch vesting locks require a non-fungible item");
+                    assert!(!batches.is_empty(), "Vesting requires at least one batch");
+                    assert!(
+                        batches.windows(2).all(|pair| pair[0].0 < pair[1].0),
+                        "Vesting batches must be in chronological order"
+                    );
+                    assert!(
+                        batches.iter().all(|(_, ids)| !ids.is_empty()),
+                        "Vesting batches must not be empty"
+                    );
+                    let ids: IndexSet<NonFungibleLocalId> = batches.iter().flat_map(|(_, ids)| ids.iter().cloned()).collect();
+                    assert!(
+                        ids.len() == batches.iter().map(|(_, ids)| ids.len()).sum::<usize>()
+                            && ids == item.as_non_fungible().non_fungible_local_ids(),
+                        "Vesting batches must contain each locked id exactly once"
+                    );
+                    batches.last().unwrap().0
+                }
+            };
+
+            // Assert the locker is not paused, the item is allowed and collect the lock fee
+            let mut items = vec![item];
+            self.assert_not_paused();
+            self.assert_allowed(&items);
+            self.collect_fee(&mut items, fee_payment);
+
+            self.create_lock(items, Some(unlockable_at), Some(schedule), display, options)
+        }
+
+        /// Claims the vested, not yet claimed, part of a vesting lock.
+        /// 
+        /// Claiming everything left in the lock marks the lock as unlocked.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt_proof` - A proof of the lock receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the claimed items.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventClaimVested` - An event emitted when vested tokens are claimed.
+        /// * `EventUnlock` - An event emitted instead when everything left in the lock is claimed.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock has no vesting schedule.
+        /// * If the lock has already been unlocked.
+        /// * If there is nothing to claim.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// * If the unlock condition of the lock is combined with AND and not met.
+        /// 
+        pub fn claim_vested(&mut self, lock_receipt_proof: Proof) -> Bucket {
+            // Get the id and the lock receipt data
+            let lock_receipt = self.check_lock_receipt(lock_receipt_proof);
+            let id = lock_receipt.local_id();
+            let lock_receipt_data = lock_receipt.data();
+
+            // Claim the vested part
+            self.claim_items(id, lock_receipt_data, Clock::current_time(TimePrecisionV2::Second))
+        }
+
+        /// Revokes the unvested part of a revocable vesting lock, the vested part stays claimable by the receipt holder.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_id` - The id of the lock to revoke.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the unvested items.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventRevoke` - An event emitted when a lock is revoked.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock does not exist.
+        /// * If the lock is not revocable or its revoke rule is not satisfied.
+        /// * If the lock has already been unlocked or revoked.
+        /// * If there is nothing to revoke.
+        /// 
+        pub fn revoke(&mut self, lock_id: NonFungibleLocalId) -> Bucket {
+            // Get the lock receipt data
+            let lock_receipt_data = self.get_lock_receipt_data(&lock_id);
+            let lock_receipt_manager = self.lock_receipt_manager_of(&lock_id);
+
+            // Get the current time
+            let current_time = Clock::current_time(TimePrecisionV2::Second);
+
+            // Assert the lock is revocable by the caller and still locked
+            let revoke_rule = lock_receipt_data.options.revoke_rule.clone().expect("Lock is not revocable");
+            Runtime::assert_access_rule(revoke_rule);
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+            assert!(lock_receipt_data.revoked_at.is_none(), "Lock has already been revoked");
+            let schedule = lock_receipt_data.schedule.expect("Lock has no vesting schedule");
+
+            // Take the unvested part from the vault
+            let mut vault = self.vaults.get_mut(&lock_id).unwrap();
+            let (revoked, vested_contents) = match lock_receipt_data.locked_contents {
+                LockContents::Fungible(total) => {
+                    let vested = schedule.vested_amount(total, current_time);
+                    let unvested = vault.amount() - (vested - lock_receipt_data.claimed_amount);
+                    assert!(unvested > Decimal::ZERO, "Nothing to revoke");
+
+                    let revoked = vault.take_advanced(unvested, WithdrawStrategy::Rounded(RoundingMode::ToZero));
+                    (revoked, LockContents::Fungible(vested))
+                },
+                LockContents::NonFungible(locked_ids) => {
+                    let released_ids = schedule.released_ids(current_time);
+                    let (vested_ids, unvested_ids): (IndexSet<NonFungibleLocalId>, IndexSet<NonFungibleLocalId>) = locked_ids
+                        .into_iter()
+                        .partition(|id| released_ids.contains(id));
+                    assert!(!unvested_ids.is_empty(), "Nothing to revoke");
+
+                    let revoked = vault.as_non_fungible().take_non_fungibles(&unvested_ids).into();
+                    (revoked, LockContents::NonFungible(vested_ids))
+                },
+            };
+            drop(vault);
+            let revoked_contents = Self::contents_of(&revoked);
+            self.record_withdrawal(lock_receipt_data.resource, &revoked_contents);
+
+            // Update the lock receipt, the vested part is unlockable from now on
+            lock_receipt_manager.update_non_fungible_data(&lock_id, "locked_contents", vested_contents.clone());
+            lock_receipt_manager.update_non_fungible_data(&lock_id, "unlockable_at", Some(current_time));
+            lock_receipt_manager.update_non_fungible_data(&lock_id, "revoked_at", Some(current_time));
+
+            // Emit revoke event
+            Runtime::emit_event(EventRevoke {
+                lock_id,
+                resource: lock_receipt_data.resource,
+                revoked_contents,
+                vested_contents,
+                revoked_at: current_time,
+            });
+
+            // Return the unvested items
+            revoked
+        }
+        
+        /// Adds more of the locked resource to an existing lock.
+        /// 
+        /// The lock fee is charged on the added item the same way as on a new lock.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt_proof` - A proof of the lock receipt.
+        /// * `item` - The item to add to the lock.
+        /// * `fee_payment` - The payment of a flat lock fee, if any.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventAddToLock` - An event emitted when an item is added to a lock.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock is permanent or vesting.
+        /// * If the lock has already been unlocked.
+        /// * If the item resource does not match the locked resource.
+        /// * If the locker is paused.
+        /// * If the resource policy does not allow the item.
+        /// * If the fee payment does not match the lock fee.
+        /// 
+        pub fn add_to_lock(&mut self, lock_receipt_proof: Proof, item: Bucket, fee_payment: Option<Bucket>) {
+            // Get the id and the lock receipt data
+            let lock_receipt = self.check_lock_receipt(lock_receipt_proof);
+            let id = lock_receipt.local_id();
+            let lock_receipt_data = lock_receipt.data();
+
+            // Assert the lock is not permanent or vesting and the item matches the locked resource
+            Self::assert_not_permanent(&lock_receipt_data);
+            assert!(lock_receipt_data.schedule.is_none(), "Vesting locks can not be added to");
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+            Self::assert_single_resource(&lock_receipt_data);
+            assert!(
+                item.resource_address() == lock_receipt_data.resource,
+                "Invalid item, locked resource: {:?}", lock_receipt_data.resource
+            );
+            self.assert_not_paused();
+            let mut items = vec![item];
+            self.assert_allowed(&items);
+            self.collect_fee(&mut items, fee_payment);
+            let item = items.pop().unwrap();
+
+            // Combine the locked and added contents
+            let added_contents = Self::contents_of(&item);
+            let locked_contents = match (lock_receipt_data.locked_contents, added_contents.clone()) {
+                (LockContents::Fungible(locked), LockContents::Fungible(added)) => LockContents::Fungible(locked + added),
+                (LockContents::NonFungible(mut locked), LockContents::NonFungible(added)) => {
+                    locked.extend(added);
+                    LockContents::NonFungible(locked)
+                },
+                _ => unreachable!(),
+            };
+
+            // Deposit the item into the vault
+            self.vaults.get_mut(id).unwrap().put(item);
+            self.record_deposit(lock_receipt_data.resource, &added_contents);
+
+            // Update the lock receipt
+            self.lock_receipt_manager_of(id).update_non_fungible_data(id, "locked_contents", locked_contents.clone());
+
+            // Emit add to lock event
+            Runtime::emit_event(EventAddToLock {
+                lock_id: id.clone(),
+                resource: lock_receipt_data.resource,
+                added_contents,
+                locked_contents,
+                added_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+        }
+
+        /// Moves the unlock time of a lock further into the future.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt_proof` - A proof of the lock receipt.
+        /// * `new_unlockable_at` - The new unlock time, `None` locks the item forever.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventExtend` - An event emitted when a lock is extended.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock is permanent, vesting or a hash lock.
+        /// * If the lock has already been unlocked.
+        /// * If the new unlock time is not later than the current one.
+        /// 
+        pub fn extend_lock(&mut self, lock_receipt_proof: Proof, new_unlockable_at: Option<Instant>) {
+            // Get the id and the lock receipt data
+            let lock_receipt = self.check_lock_receipt(lock_receipt_proof);
+            let id = lock_receipt.local_id();
+            let lock_receipt_data = lock_receipt.data();
+
+            // Assert the lock is not permanent, vesting or a hash lock whose timeout the recipient relies on
+            Self::assert_not_permanent(&lock_receipt_data);
+            assert!(lock_receipt_data.schedule.is_none(), "Vesting locks can not be extended");
+            assert!(lock_receipt_data.options.hash_lock.is_none(), "Hash locks can not be extended");
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+
+            // Assert the new unlock time is later
+            let old_unlockable_at = lock_receipt_data.unlockable_at;
+            assert!(
+                match (old_unlockable_at, new_unlockable_at) {
+                    (Some(old), Some(new)) => new > old,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                },
+                "Lock can only be extended, unlockable at: {:?}", old_unlockable_at
+            );
+
+            // Update the lock receipt
+            self.lock_receipt_manager_of(id).update_non_fungible_data(id, "unlockable_at", new_unlockable_at);
+            self.lock_receipt_manager_of(id).update_non_fungible_data(id, "kind", LockKind::of(new_unlockable_at, &lock_receipt_data.options));
+
+            // Emit extend event
+            Runtime::emit_event(EventExtend {
+                lock_id: id.clone(),
+                resource: lock_receipt_data.resource,
+                old_unlockable_at,
+                new_unlockable_at,
+                extended_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+        }
+
+        /// Unlocks part of a fungible item, leaving the lock receipt with its holder.
+        /// 
+        /// Unlocking the whole remaining amount marks the lock as unlocked.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt_proof` - A proof of the lock receipt.
+        /// * `amount` - The amount to unlock.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the unlocked amount.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventPartialUnlock` - An event emitted when part of an item is unlocked.
+        /// * `EventUnlock` - An event emitted instead when the whole remaining amount is unlocked.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock has already been unlocked.
+        /// * If the lock is not unlockable.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// * If the locked item is not fungible.
+        /// * If the amount is not positive or exceeds the locked amount.
+        /// 
+        pub fn partial_unlock(&mut self, lock_receipt_proof: Proof, amount: Decimal) -> Bucket {
+            // Get the id and the lock receipt data
+            let lock_receipt = self.check_lock_receipt(lock_receipt_proof);
+            let id = lock_receipt.local_id();
+            let lock_receipt_data = lock_receipt.data();
+
+            // Get the current time
+            let current_time = Clock::current_time(TimePrecisionV2::Second);
+
+            // Assert item is unlockable
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+            Self::assert_unlockable(&lock_receipt_data, current_time);
+            Self::assert_single_resource(&lock_receipt_data);
+
+            // Assert the amount is valid
+            let locked_amount = match lock_receipt_data.locked_contents {
+                LockContents::Fungible(locked_amount) => locked_amount,
+                LockContents::NonFungible(_) => panic!("Partial unlocks require a fungible item"),
+            };
+            let mut vault = self.vaults.get_mut(id).unwrap();
+            assert!(
+                amount > Decimal::ZERO && amount <= vault.amount(),
+                "Invalid amount, locked amount: {:?}", vault.amount()
+            );
+
+            // Take the amount from the vault
+            let item = vault.take(amount);
+            let vault_emptied = vault.is_empty();
+            drop(vault);
+
+            // Update the lock receipt
+            let locked_contents = LockContents::Fungible(locked_amount - amount);
+            self.lock_receipt_manager_of(id).update_non_fungible_data(id, "locked_contents", locked_contents.clone());
+
+            // Close the lock if nothing is left in it, otherwise emit partial unlock event
+            if vault_emptied {
+                self.close_lock(id, &lock_receipt_data, Self::contents_of(&item), current_time);
+            } else {
+                self.record_withdrawal(lock_receipt_data.resource, &Self::contents_of(&item));
+                Runtime::emit_event(EventPartialUnlock {
+                    lock_id: id.clone(),
+                    resource: lock_receipt_data.resource,
+                    amount,
+                    locked_contents,
+                    unlocked_at: current_time,
+                });
+            }
+
+            // Return the item
+            item
+        }
+
+        /// Takes one or more lock receipts and returns the items they unlock.
+        /// 
+        /// A timed vesting lock that has not fully vested releases only its vested, not yet claimed, part, the same
+        /// as `claim_vested`, and its lock receipt is returned so the rest can be unlocked later, unless nothing is
+        /// left in the lock.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipts` - The lock receipts to unlock.
+        /// 
+        /// # Returns
+        /// 
+        /// A vector of buckets, each containing the item that was unlocked, followed by a bucket containing the lock
+        /// receipts of locks still vesting, if any.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventUnlock` - An event emitted when an item is unlocked.
+        /// * `EventClaimVested` - An event emitted when the vested part of a lock still vesting is released.
+        /// * `EventRefund` - An event emitted when a hash lock is refunded.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipts are invalid.
+        /// * If any lock is not unlockable by its time or conditions and its early unlock rule, if any, is not satisfied.
+        /// * If a lock still vesting has nothing to claim.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// 
+        pub fn unlock(&mut self, lock_receipts: Bucket) -> Vec<Bucket> {
+            // Assert valid lock receipts
+            assert!(
+                self.is_lock_receipt_resource(lock_receipts.resource_address()),
+                "Invalid lock receipts"
+            );
+
+            // Get the current time
+            let current_time = Clock::current_time(TimePrecisionV2::Second);
+
+            // Check each lock receipt
+            let mut items = Vec::new();
+            let mut vesting_ids = IndexSet::new();
+            for lock_receipt in lock_receipts.as_non_fungible().non_fungibles::<LockReceipt>() {
+                // Get the id and the lock receipt data
+                let id = lock_receipt.local_id();
+                let lock_receipt_data = lock_receipt.data();
+
+                // Release the vested part of a lock still vesting, or unlock the items
+                if Self::is_vesting(&lock_receipt_data, current_time) {
+                    items.push(self.claim_items(id, lock_receipt_data, current_time));
+                    if !self.vaults.get(id).unwrap().is_empty() {
+                        vesting_ids.insert(id.clone());
+                    }
+                } else {
+                    items.extend(self.unlock_items(id, lock_receipt_data, current_time));
+                }
+            }
+
+            // Keep the lock receipts of locks still vesting and store the used lock receipts
+            if !vesting_ids.is_empty() {
+                items.push(lock_receipts.as_non_fungible().take_non_fungibles(&vesting_ids).into());
+            }
+            self.store_used_lock_receipts(lock_receipts);
+
+            // Return the items
+            items
+        }
+
+        /// Unlocks a lock by proof of its lock receipt, leaving the lock receipt with its holder.
+        /// 
+        /// Soulbound lock receipts can not be withdrawn, so their locks are unlocked, and their hash locks refunded,
+        /// this way.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt_proof` - A proof of the lock receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// A vector of buckets, each containing an item that was unlocked.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventUnlock` - An event emitted when an item is unlocked.
+        /// * `EventRefund` - An event emitted when a hash lock is refunded.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock has already been unlocked.
+        /// * If the lock is not unlockable by its time or conditions and its early unlock rule, if any, is not satisfied.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// 
+        pub fn unlock_with_proof(&mut self, lock_receipt_proof: Proof) -> Vec<Bucket> {
+            // Get the id and the lock receipt data
+            let lock_receipt = self.check_lock_receipt(lock_receipt_proof);
+            let id = lock_receipt.local_id();
+            let lock_receipt_data = lock_receipt.data();
+
+            // Unlock the items
+            self.unlock_items(id, lock_receipt_data, Clock::current_time(TimePrecisionV2::Second))
+        }
+
+        /// Unlocks a lock with a beneficiary and deposits the items into the beneficiary account.
+        /// 
+        /// Anyone can call this once the lock is unlockable, the lock receipt stays with its holder.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_id` - The id of the lock to unlock.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventUnlock` - An event emitted when an item is unlocked.
+        /// * `EventRefund` - An event emitted when a hash lock is refunded.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock does not exist.
+        /// * If the lock has no beneficiary.
+        /// * If the lock is not unlockable.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// * If the beneficiary account does not accept the items.
+        /// 
+        pub fn unlock_to_beneficiary(&mut self, lock_id: NonFungibleLocalId) {
+            // Get the lock receipt data
+            let lock_receipt_data = self.get_lock_receipt_data(&lock_id);
+            let mut beneficiary = lock_receipt_data.options.beneficiary.expect("Lock has no beneficiary");
+
+            // Unlock the items and deposit them into the beneficiary account
+            let items = self.unlock_items(&lock_id, lock_receipt_data, Clock::current_time(TimePrecisionV2::Second));
+            beneficiary.try_deposit_batch_or_abort(items, None);
+        }
+
+        /// Releases a hash lock to its recipient by revealing the preimage of its hash before the timeout.
+        /// 
+        /// Anyone can call this, the lock receipt stays with its holder.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_id` - The id of the lock to claim.
+        /// * `preimage` - The secret preimage of the hash.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventUnlock` - An event emitted when an item is unlocked.
+        /// * `EventClaimWithPreimage` - An event emitted with the revealed preimage.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock does not exist.
+        /// * If the lock has no hash lock.
+        /// * If the lock has already been unlocked.
+        /// * If the timeout has passed.
+        /// * If the preimage does not match the hash.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// * If the recipient account does not accept the items.
+        /// 
+        pub fn claim_with_preimage(&mut self, lock_id: NonFungibleLocalId, preimage: Vec<u8>) {
+            // Get the lock receipt data
+            let lock_receipt_data = self.get_lock_receipt_data(&lock_id);
+            let hash_lock = lock_receipt_data.options.hash_lock.clone().expect("Lock has no hash lock");
+
+            // Get the current time
+            let current_time = Clock::current_time(TimePrecisionV2::Second);
+
+            // Assert the hash lock can be claimed
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+            assert!(
+                lock_receipt_data.unlockable_at.is_some_and(|unlockable_at| current_time < unlockable_at),
+                "Hash lock has expired, timeout: {:?}", lock_receipt_data.unlockable_at
+            );
+            assert!(HashLock::hash_of(&preimage) == hash_lock.hash, "Invalid preimage");
+            Self::assert_unlock_rule(&lock_receipt_data);
+
+            // Emit claim with preimage event
+            Runtime::emit_event(EventClaimWithPreimage {
+                lock_id: lock_id.clone(),
+                resource: lock_receipt_data.resource,
+                preimage,
+                recipient: hash_lock.recipient,
+                claimed_at: current_time,
+            });
+
+            // Release the items and deposit them into the recipient account
+            let mut recipient = hash_lock.recipient;
+            let items = self.release_items(&lock_id, lock_receipt_data, current_time);
+            recipient.try_deposit_batch_or_abort(items, None);
+        }
+
+        /// Reclaims the items of a hash lock after its timeout.
+        /// 
+        /// Takes the lock receipt in a bucket, so soulbound lock receipts, which can not be withdrawn, are refunded
+        /// with `unlock_with_proof` instead.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt` - The lock receipt of the hash lock.
+        /// 
+        /// # Returns
+        /// 
+        /// A vector of buckets, each containing an item that was refunded.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventUnlock` - An event emitted when an item is unlocked.
+        /// * `EventRefund` - An event emitted when a hash lock is refunded.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock has no hash lock.
+        /// * If the lock has already been unlocked.
+        /// * If the timeout has not passed.
+        /// * If the unlock rule of the lock, if any, is not satisfied.
+        /// 
+        pub fn refund(&mut self, lock_receipt: Bucket) -> Vec<Bucket> {
+            // Assert valid lock receipt
+            assert!(
+                self.is_lock_receipt_resource(lock_receipt.resource_address()),
+                "Invalid lock receipt"
+            );
+
+            // Get the id and the lock receipt data
+            let non_fungible = lock_receipt.as_non_fungible().non_fungible::<LockReceipt>();
+            let id = non_fungible.local_id();
+            let lock_receipt_data = non_fungible.data();
+            assert!(lock_receipt_data.options.hash_lock.is_some(), "Lock has no hash lock");
+
+            // Unlock the items
+            let items = self.unlock_items(id, lock_receipt_data, Clock::current_time(TimePrecisionV2::Second));
+
+            // Store the used lock receipt
+            self.store_used_lock_receipts(lock_receipt);
+
+            // Return the items
+            items
+        }
+
+        /// Splits a lock into multiple locks with the same unlock time.
+        /// 
+        /// Takes the lock receipt in a bucket, so locks with soulbound lock receipts, which can not be withdrawn, can
+        /// not be split.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipt` - The lock receipt to split.
+        /// * `parts` - The contents of each new lock, any remainder is locked in one more new lock.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the new lock receipts.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventLock` - An event emitted for each new lock.
+        /// * `EventSplit` - An event emitted when a lock is split.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipt is invalid.
+        /// * If the lock is permanent or vesting.
+        /// * If the lock has already been unlocked.
+        /// * If any part is empty, of the wrong kind or not in the lock.
+        /// 
+        pub fn split(&mut self, lock_receipt: Bucket, parts: Vec<LockContents>) -> Bucket {
+            // Assert valid lock receipt
+            assert!(
+                self.is_lock_receipt_resource(lock_receipt.resource_address()),
+                "Invalid lock receipt"
+            );
+
+            // Get the id and the lock receipt data
+            let non_fungible = lock_receipt.as_non_fungible().non_fungible::<LockReceipt>();
+            let id = non_fungible.local_id();
+            let lock_receipt_data = non_fungible.data();
+
+            // Assert the lock is not permanent or vesting
+            Self::assert_not_permanent(&lock_receipt_data);
+            assert!(lock_receipt_data.schedule.is_none(), "Vesting locks can not be split");
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+            Self::assert_single_resource(&lock_receipt_data);
+            assert!(!parts.is_empty(), "Invalid split, no parts");
+
+            // Take each part from the vault
+            let mut vault = self.vaults.get_mut(id).unwrap();
+            let mut items = Vec::new();
+            for part in parts {
+                let item = match part {
+                    LockContents::Fungible(amount) => {
+                        assert!(
+                            vault.resource_manager().resource_type().is_fungible() && amount > Decimal::ZERO && amount <= vault.amount(),
+                            "Invalid split, locked contents: {:?}", lock_receipt_data.locked_contents
+                        );
+                        vault.take(amount)
+                    },
+                    LockContents::NonFungible(ids) => {
+                        assert!(
+                            !vault.resource_manager().resource_type().is_fungible() && !ids.is_empty()
+                                && ids.iter().all(|id| vault.as_non_fungible().contains_non_fungible(id)),
+                            "Invalid split, locked contents: {:?}", lock_receipt_data.locked_contents
+                        );
+                        vault.as_non_fungible().take_non_fungibles(&ids).into()
+                    },
+                };
+                items.push(item);
+            }
+            if !vault.is_empty() {
+                items.push(vault.take_all());
+            }
+            drop(vault);
+            self.record_unlock(lock_receipt_data.resource, &lock_receipt_data.locked_contents);
+
+            // Lock each part with the same unlock time
+            let mut split_lock_ids = Vec::new();
+            let mut split_lock_receipts = Bucket::new(lock_receipt.resource_address());
+            for item in items {
+                let split_lock_receipt = self.create_lock(
+                    vec![item],
+                    lock_receipt_data.unlockable_at,
+                    None,
+                    LockDisplay {
+                        name: lock_receipt_data.name.clone(),
+                        description: lock_receipt_data.description.clone(),
+                        key_image_url: lock_receipt_data.key_image_url.clone(),
+                    },
+                    lock_receipt_data.options.clone(),
+                );
+                split_lock_ids.push(split_lock_receipt.as_non_fungible().non_fungible_local_id());
+                split_lock_receipts.put(split_lock_receipt);
+            }
+
+            // Emit split event
+            Runtime::emit_event(EventSplit {
+                lock_id: id.clone(),
+                resource: lock_receipt_data.resource,
+                split_lock_ids,
+                split_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+
+            // Store the used lock receipt
+            self.store_used_lock_receipts(lock_receipt);
+
+            // Return the new lock receipts
+            split_lock_receipts
+        }
+
+        /// Merges multiple locks of the same resource into one lock with the latest unlock time.
+        /// 
+        /// Takes the lock receipts in a bucket, so locks with soulbound lock receipts, which can not be withdrawn, can
+        /// not be merged.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_receipts` - The lock receipts to merge.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the new lock receipt.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventLock` - An event emitted for the new lock.
+        /// * `EventMerge` - An event emitted when locks are merged.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock receipts are invalid.
+        /// * If there are less than two lock receipts.
+        /// * If the locks are of different resources.
+        /// * If any lock is permanent, vesting or a hash lock.
+        /// * If any lock has already been unlocked.
+        /// 
+        pub fn merge(&mut self, lock_receipts: Bucket) -> Bucket {
+            // Assert valid lock receipts
+            assert!(
+                self.is_lock_receipt_resource(lock_receipts.resource_address()),
+                "Invalid lock receipts"
+            );
+            let non_fungibles = lock_receipts.as_non_fungible().non_fungibles::<LockReceipt>();
+            assert!(non_fungibles.len() >= 2, "Merge requires at least two lock receipts");
+
+            // Take each item from its vault
+            let first_lock_receipt_data = non_fungibles[0].data();
+            let resource = first_lock_receipt_data.resource;
+            let mut unlockable_at = first_lock_receipt_data.unlockable_at;
+            let mut merged_lock_ids = Vec::new();
+            let mut item = Bucket::new(resource);
+            for lock_receipt in non_fungibles {
+                // Get the id and the lock receipt data
+                let id = lock_receipt.local_id();
+                let lock_receipt_data = lock_receipt.data();
+
+                // Assert the lock can be merged
+                assert!(lock_receipt_data.resource == resource, "Merged locks must be of the same resource");
+                assert!(lock_receipt_data.options == first_lock_receipt_data.options, "Merged locks must have the same options");
+                assert!(lock_receipt_data.schedule.is_none(), "Vesting locks can not be merged");
+                assert!(lock_receipt_data.options.hash_lock.is_none(), "Hash locks can not be merged");
+                assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+                Self::assert_not_permanent(&lock_receipt_data);
+                Self::assert_single_resource(&lock_receipt_data);
+
+                // Keep the latest unlock time, where never is the latest
+                unlockable_at = unlockable_at.zip(lock_receipt_data.unlockable_at).map(|(a, b)| a.max(b));
+
+                // Take the item from the vault
+                let merged_item = self.vaults.get_mut(id).unwrap().take_all();
+                self.record_unlock(resource, &Self::contents_of(&merged_item));
+                item.put(merged_item);
+                merged_lock_ids.push(id.clone());
+            }
+
+            // Lock the merged item
+            let lock_receipt = self.create_lock(
+                vec![item],
+                unlockable_at,
+                None,
+                LockDisplay {
+                    name: first_lock_receipt_data.name,
+                    description: first_lock_receipt_data.description,
+                    key_image_url: first_lock_receipt_data.key_image_url,
+                },
+                first_lock_receipt_data.options,
+            );
+
+            // Emit merge event
+            Runtime::emit_event(EventMerge {
+                lock_id: lock_receipt.as_non_fungible().non_fungible_local_id(),
+                resource,
+                merged_lock_ids,
+                merged_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+
+            // Store the used lock receipts
+            self.store_used_lock_receipts(lock_receipts);
+
+            // Return the new lock receipt
+            lock_receipt
+        }
+
+        /// Returns the current state of a lock.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `lock_id` - The id of the lock.
+        /// 
+        /// # Returns
+        /// 
+        /// The lock receipt data and the balances currently locked.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the lock does not exist.
+        /// 
+        pub fn get_lock(&self, lock_id: NonFungibleLocalId) -> LockInfo {
+            let lock_receipt = self.get_lock_receipt_data(&lock_id);
+            let balance = self.vaults.get(&lock_id).unwrap().amount();
+            let additional_balances = self.additional_vaults.get(&lock_id)
+                .map(|vaults| vaults.iter().map(|(resource, vault)| (*resource, vault.amount())).collect())
+                .unwrap_or_default();
+
+            LockInfo {
+                lock_id,
+                lock_receipt,
+                balance,
+                additional_balances,
+            }
+        }
+
+        /// Returns a page of the ids of the locks of a resource, including locks that have been unlocked, in the order
+        /// they were created.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The locked resource.
+        /// * `offset` - The number of locks to skip.
+        /// * `limit` - The maximum number of lock ids to return.
+        /// 
+        /// # Returns
+        /// 
+        /// The ids of the locks.
+        /// 
+        pub fn get_locks_by_resource(&self, resource: ResourceAddress, offset: u64, limit: u64) -> Vec<NonFungibleLocalId> {
+            let end = self.get_lock_count_by_resource(resource).min(offset.saturating_add(limit));
+            (offset..end)
+                .map(|index| self.locks_by_resource.get(&LockIndexKey { resource, index }).unwrap().clone())
+                .collect()
+        }
+
+        /// Returns the number of locks of a resource, including locks that have been unlocked.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The locked resource.
+        /// 
+        /// # Returns
+        /// 
+        /// The number of locks.
+        /// 
+        pub fn get_lock_count_by_resource(&self, resource: ResourceAddress) -> u64 {
+            self.lock_counts_by_resource.get(&resource)
+                .map(|count| *count)
+                .unwrap_or_default()
+        }
+
+        /// Returns the amount of a resource that is currently locked.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The locked resource.
+        /// 
+        /// # Returns
+        /// 
+        /// The locked amount, or number of non-fungibles.
+        /// 
+        pub fn total_locked(&self, resource: ResourceAddress) -> Decimal {
+            let stats = self.get_resource_lock_stats(resource);
+            stats.locked_amount + Decimal::from(stats.locked_non_fungibles)
+        }
+
+        /// Returns the aggregate statistics of the locks of a resource.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The locked resource.
+        /// 
+        /// # Returns
+        /// 
+        /// The lock stats of the resource.
+        /// 
+        pub fn get_resource_lock_stats(&self, resource: ResourceAddress) -> ResourceLockStats {
+            self.resource_lock_stats.get(&resource)
+                .map(|stats| stats.clone())
+                .unwrap_or_default()
+        }
+
+        /// Returns the aggregate statistics of the locks of a resource as metadata-friendly strings.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The locked resource.
+        /// 
+        /// # Returns
+        /// 
+        /// The lock stats of the resource by name, times are in seconds since the unix epoch.
+        /// 
+        pub fn get_resource_lock_stats_metadata(&self, resource: ResourceAddress) -> IndexMap<String, String> {
+            self.get_resource_lock_stats(resource).to_metadata()
+        }
+
+        /// Returns the fee charged when creating a lock.
+        /// 
+        /// # Returns
+        /// 
+        /// The lock fee, if any.
+        /// 
+        pub fn get_fee(&self) -> Option<LockFee> {
+            self.fee.clone()
+        }
+
+        /// Sets the fee charged when creating a lock.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `fee` - The new lock fee, `None` makes locking free.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventFeeChange` - An event emitted when the lock fee is changed.
+        /// 
+        /// # Panics
+        /// 
+        /// * If a flat fee amount is not positive.
+        /// * If a basis point fee is not between 1 and `MAX_FEE_BASIS_POINTS`.
+        /// 
+        pub fn set_fee(&mut self, fee: Option<LockFee>) {
+            // Assert the fee is valid
+            match &fee {
+                Some(LockFee::Flat { amount, .. }) => assert!(*amount > Decimal::ZERO, "Invalid fee: {:?}", fee),
+                Some(LockFee::BasisPoints(basis_points)) => {
+                    assert!(*basis_points > 0 && *basis_points <= MAX_FEE_BASIS_POINTS, "Invalid fee: {:?}", fee)
+                },
+                None => {},
+            }
+
+            // Emit fee change event
+            Runtime::emit_event(EventFeeChange {
+                old_fee: self.fee.clone(),
+                new_fee: fee.clone(),
+                changed_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+
+            self.fee = fee;
+        }
+
+        /// Withdraws the collected fees of a resource.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The resource of the fees.
+        /// 
+        /// # Returns
+        /// 
+        /// A bucket containing the collected fees.
+        /// 
+        /// # Panics
+        /// 
+        /// * If no fees of the resource have been collected.
+        /// 
+        pub fn withdraw_fees(&mut self, resource: ResourceAddress) -> Bucket {
+            self.fee_vaults.get_mut(&resource)
+                .expect("No fees collected")
+                .take_all()
+        }
+
+        /// Returns the policy deciding which resources can be locked.
+        /// 
+        /// # Returns
+        /// 
+        /// The resource policy.
+        /// 
+        pub fn get_resource_policy(&self) -> ResourcePolicy {
+            self.resource_policy.clone()
+        }
+
+        /// Sets the policy deciding which resources can be locked, existing locks are not affected.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `policy` - The new resource policy.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventResourcePolicyChange` - An event emitted when the resource policy is changed.
+        /// 
+        pub fn set_resource_policy(&mut self, policy: ResourcePolicy) {
+            self.resource_policy = policy;
+
+            // Emit resource policy change event
+            Runtime::emit_event(EventResourcePolicyChange {
+                policy: self.resource_policy.clone(),
+                changed_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+        }
+
+        /// Adds a resource to or removes a resource from the list of the resource policy.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - The resource to list or unlist.
+        /// * `listed` - Whether the resource is listed.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventResourcePolicyChange` - An event emitted when the resource policy is changed.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the policy allows all resources.
+        /// 
+        pub fn set_resource_listed(&mut self, resource: ResourceAddress, listed: bool) {
+            // Update the list of the policy
+            let resources = match &mut self.resource_policy {
+                ResourcePolicy::AllowAll => panic!("Resource policy has no list"),
+                ResourcePolicy::Allowlist(resources) | ResourcePolicy::Denylist(resources) => resources,
+            };
+            if listed {
+                resources.insert(resource);
+            } else {
+                resources.shift_remove(&resource);
+            }
+
+            // Emit resource policy change event
+            Runtime::emit_event(EventResourcePolicyChange {
+                policy: self.resource_policy.clone(),
+                changed_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+        }
+
+        /// Returns whether the locker is paused.
+        /// 
+        /// # Returns
+        /// 
+        /// `true` if new locks and additions to locks are blocked.
+        /// 
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Pauses the locker, blocking new locks and additions to locks while unlocking stays available.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventPause` - An event emitted when the locker is paused.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the locker is already paused.
+        /// 
+        pub fn pause(&mut self) {
+            self.assert_not_paused();
+            self.paused = true;
+
+            // Emit pause event
+            Runtime::emit_event(EventPause {
+                paused_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+        }
+
+        /// Unpauses the locker.
+        /// 
+        /// # Emits
+        /// 
+        /// * `EventUnpause` - An event emitted when the locker is unpaused.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the locker is not paused.
+        /// 
+        pub fn unpause(&mut self) {
+            assert!(self.paused, "Locker is not paused");
+            self.paused = false;
+
+            // Emit unpause event
+            Runtime::emit_event(EventUnpause {
+                unpaused_at: Clock::current_time(TimePrecisionV2::Second),
+            });
+        }
+
+        /// Returns the lock receipt data of a lock.
+        fn get_lock_receipt_data(&self, lock_id: &NonFungibleLocalId) -> LockReceipt {
+            let lock_receipt_manager = self.lock_receipt_manager_of(lock_id);
+            assert!(lock_receipt_manager.non_fungible_exists(lock_id), "Lock does not exist");
+            lock_receipt_manager.get_non_fungible_data::<LockReceipt>(lock_id)
+        }
+
+        /// Returns the manager of the lock receipt of a lock, depending on whether it is soulbound.
+        fn lock_receipt_manager_of(&self, lock_id: &NonFungibleLocalId) -> ResourceManager {
+            if self.soulbound_lock_receipt_manager.non_fungible_exists(lock_id) {
+                self.soulbound_lock_receipt_manager
+            } else {
+                self.lock_receipt_manager
+            }
+        }
+
+        /// Returns whether a resource is one of the lock receipt resources.
+        fn is_lock_receipt_resource(&self, resource: ResourceAddress) -> bool {
+            resource == self.lock_receipt_manager.address() || resource == self.soulbound_lock_receipt_manager.address()
+        }
+
+        /// Stores used lock receipts in the vault of their resource.
+        fn store_used_lock_receipts(&mut self, lock_receipts: Bucket) {
+            if lock_receipts.resource_address() == self.soulbound_lock_receipt_manager.address() {
+                self.used_soulbound_lock_receipts.put(lock_receipts);
+            } else {
+                self.used_lock_receipts.put(lock_receipts);
+            }
+        }
+
+        /// Takes the vested, not yet claimed, part of a vesting lock from its vault, and marks the lock as unlocked if
+        /// nothing is left in it.
+        fn claim_items(&mut self, id: &NonFungibleLocalId, lock_receipt_data: LockReceipt, current_time: Instant) -> Bucket {
+            let lock_receipt_manager = self.lock_receipt_manager_of(id);
+
+            // Assert the lock is vesting and still locked
+            Self::assert_unlock_rule(&lock_receipt_data);
+            let schedule = lock_receipt_data.schedule.as_ref().expect("Lock has no vesting schedule");
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+
+            // Assert an unlock condition combined with AND is met, it applies to every release of the lock
+            if let Some(unlock_condition) = &lock_receipt_data.options.unlock_condition {
+                if unlock_condition.mode == ConditionMode::And {
+                    assert!(unlock_condition.is_met(), "Unlock condition is not met");
+                }
+            }
+
+            // Take the claimable part from the vault
+            let mut vault = self.vaults.get_mut(id).unwrap();
+            let claimed = match &lock_receipt_data.locked_contents {
+                LockContents::Fungible(total) => {
+                    // Calculate the claimable amount, everything left in a revoked lock has vested
+                    let claimable = match lock_receipt_data.revoked_at {
+                        Some(_) => vault.amount(),
+                        None => schedule.vested_amount(*total, current_time) - lock_receipt_data.claimed_amount,
+                    };
+                    assert!(claimable > Decimal::ZERO, "Nothing to claim");
+
+                    vault.take_advanced(claimable, WithdrawStrategy::Rounded(RoundingMode::ToZero))
+                },
+                LockContents::NonFungible(locked_ids) => {
+                    // Get the released ids that are still locked, everything left in a revoked lock has vested
+                    let claimable: IndexSet<NonFungibleLocalId> = match lock_receipt_data.revoked_at {
+                        Some(_) => locked_ids.clone(),
+                        None => schedule.released_ids(current_time).intersection(locked_ids).cloned().collect(),
+                    };
+                    assert!(!claimable.is_empty(), "Nothing to claim");
+
+                    // Update the remaining locked ids
+                    let remaining_ids: IndexSet<NonFungibleLocalId> = locked_ids.difference(&claimable).cloned().collect();
+                    lock_receipt_manager.update_non_fungible_data(id, "locked_contents", LockContents::NonFungible(remaining_ids));
+
+                    vault.as_non_fungible().take_non_fungibles(&claimable).into()
+                },
+            };
+            let vault_emptied = vault.is_empty();
+            drop(vault);
+            let claimed_amount = lock_receipt_data.claimed_amount + claimed.amount();
+
+            // Update the lock receipt
+            lock_receipt_manager.update_non_fungible_data(id, "claimed_amount", claimed_amount);
+            lock_receipt_manager.update_non_fungible_data(id, "claimed_tranches", schedule.released_tranches(current_time));
+
+            // Close the lock if nothing is left in it, otherwise emit claim event
+            if vault_emptied {
+                self.close_lock(id, &lock_receipt_data, Self::contents_of(&claimed), current_time);
+            } else {
+                self.record_withdrawal(lock_receipt_data.resource, &Self::contents_of(&claimed));
+                Runtime::emit_event(EventClaimVested {
+                    lock_id: id.clone(),
+                    resource: lock_receipt_data.resource,
+                    amount: claimed.amount(),
+                    claimed_amount,
+                    claimed_at: current_time,
+                });
+            }
+
+            // Return the claimed items
+            claimed
+        }
+
+        /// Takes the items of an unlockable lock from their vaults and marks the lock as unlocked.
+        fn unlock_items(
+            &mut self,
+            id: &NonFungibleLocalId,
+            lock_receipt_data: LockReceipt,
+            current_time: Instant,
+        ) -> Vec<Bucket> {
+            // Assert item is unlockable
+            assert!(lock_receipt_data.unlocked_at.is_none(), "Lock has already been unlocked");
+            Self::assert_unlockable(&lock_receipt_data, current_time);
+
+            // Emit refund event, unlocking a hash lock after its timeout refunds it
+            if lock_receipt_data.options.hash_lock.is_some() {
+                Runtime::emit_event(EventRefund {
+                    lock_id: id.clone(),
+                    resource: lock_receipt_data.resource,
+                    refunded_at: current_time,
+                });
+            }
+
+            self.release_items(id, lock_receipt_data, current_time)
+        }
+
+        /// Takes the items of a lock from their vaults and marks the lock as unlocked.
+        fn release_items(
+            &mut self,
+            id: &NonFungibleLocalId,
+            lock_receipt_data: LockReceipt,
+            current_time: Instant,
+        ) -> Vec<Bucket> {
+            // Take the item from the vault
+            let mut items = vec![self.vaults.get_mut(id).unwrap().take_all()];
+
+            // Take any additional items from their vaults
+            if let Some(mut additional_vaults) = self.additional_vaults.get_mut(id) {
+                for vault in additional_vaults.values_mut() {
+                    items.push(vault.take_all());
+                }
+            }
+
+            // Update the lock receipt
+            self.lock_receipt_manager_of(id).update_non_fungible_data(id, "unlocked_at", Some(current_time));
+
+            // Update the resource stats
+            for item in items.iter() {
+                self.record_unlock(item.resource_address(), &Self::contents_of(item));
+            }
+
+            // Emit unlock event with the contents actually released, claims may have taken part of them already
+            if lock_receipt_data.additional_contents.is_empty() {
+                Runtime::emit_event(EventUnlock {
                     lock_id: id.clone(),
                     resource: lock_receipt_data.resource,
-                    locked_contents: lock_receipt_data.locked_contents,
+                    locked_contents: Self::contents_of(&items[0]),
+                    locked_at: lock_receipt_data.locked_at,
+                    unlockable_at: lock_receipt_data.unlockable_at,
+                    unlocked_at: current_time,
+                });
+            } else {
+                let locked_contents: IndexMap<ResourceAddress, LockContents> = items
+                    .iter()
+                    .map(|item| (item.resource_address(), Self::contents_of(item)))
+                    .collect();
+                Runtime::emit_event(EventUnlockMany {
+                    lock_id: id.clone(),
+                    locked_contents,
                     locked_at: lock_receipt_data.locked_at,
                     unlockable_at: lock_receipt_data.unlockable_at,
                     unlocked_at: current_time,
                 });
             }
 
-            // Store the used lock receipts
-            self.used_lock_receipts.put(lock_receipts);
-
-            // Return the items
             items
         }
+
+        /// Marks a lock emptied by a partial release as unlocked.
+        fn close_lock(
+            &mut self,
+            id: &NonFungibleLocalId,
+            lock_receipt_data: &LockReceipt,
+            released_contents: LockContents,
+            current_time: Instant,
+        ) {
+            // Update the lock receipt
+            self.lock_receipt_manager_of(id).update_non_fungible_data(id, "unlocked_at", Some(current_time));
+
+            // Update the resource stats
+            self.record_unlock(lock_receipt_data.resource, &released_contents);
+
+            // Emit unlock event
+            Runtime::emit_event(EventUnlock {
+                lock_id: id.clone(),
+                resource: lock_receipt_data.resource,
+                locked_contents: released_contents,
+                locked_at: lock_receipt_data.locked_at,
+                unlockable_at: lock_receipt_data.unlockable_at,
+                unlocked_at: current_time,
+            });
+        }
+
+        /// Asserts that the locker is not paused.
+        fn assert_not_paused(&self) {
+            assert!(!self.paused, "Locker is paused");
+        }
+
+        /// Asserts that the resource policy allows locking each item.
+        fn assert_allowed(&self, items: &[Bucket]) {
+            for item in items {
+                let resource = item.resource_address();
+                assert!(self.resource_policy.allows(&resource), "Resource not allowed: {:?}", resource);
+            }
+        }
+
+        /// Collects the lock fee from the fee payment, or as a cut of each fungible item.
+        fn collect_fee(&mut self, items: &mut [Bucket], fee_payment: Option<Bucket>) {
+            match self.fee.clone() {
+                Some(LockFee::Flat { resource, amount }) => {
+                    let fee_payment = fee_payment.expect("Lock fee payment required");
+                    assert!(
+                        fee_payment.resource_address() == resource && fee_payment.amount() == amount,
+                        "Invalid fee payment, fee: {:?}", self.fee
+                    );
+                    self.deposit_fee(fee_payment);
+                },
+                Some(LockFee::BasisPoints(basis_points)) => {
+                    assert!(fee_payment.is_none(), "No fee payment required");
+                    for item in items.iter_mut() {
+                        if item.resource_manager().resource_type().is_fungible() {
+                            let fee = item.amount() * Decimal::from(basis_points) / dec!(10000);
+                            let fee = item.take_advanced(fee, WithdrawStrategy::Rounded(RoundingMode::ToZero));
+                            self.deposit_fee(fee);
+                        }
+                    }
+                },
+                None => assert!(fee_payment.is_none(), "No fee payment required"),
+            }
+        }
+
+        /// Deposits a collected fee into the fee vault of its resource.
+        fn deposit_fee(&mut self, fee: Bucket) {
+            let resource = fee.resource_address();
+            if self.fee_vaults.get(&resource).is_some() {
+                self.fee_vaults.get_mut(&resource).unwrap().put(fee);
+            } else {
+                self.fee_vaults.insert(resource, Vault::with_bucket(fee));
+            }
+        }
+
+        /// Adds a lock to the index of locks of a resource.
+        fn index_lock(&mut self, resource: ResourceAddress, id: &NonFungibleLocalId) {
+            let index = self.get_lock_count_by_resource(resource);
+            self.locks_by_resource.insert(LockIndexKey { resource, index }, id.clone());
+            self.lock_counts_by_resource.insert(resource, index + 1);
+        }
+
+        /// Records a new lock of a resource in its stats.
+        fn record_lock(&mut self, resource: ResourceAddress, contents: &LockContents) {
+            let mut stats = self.get_resource_lock_stats(resource);
+            stats.add(contents);
+            stats.active_locks += 1;
+            self.resource_lock_stats.insert(resource, stats);
+        }
+
+        /// Records the release of a lock of a resource in its stats.
+        fn record_unlock(&mut self, resource: ResourceAddress, contents: &LockContents) {
+            let mut stats = self.get_resource_lock_stats(resource);
+            stats.remove(contents);
+            stats.active_locks -= 1;
+            self.resource_lock_stats.insert(resource, stats);
+        }
+
+        /// Records an item added to an existing lock of a resource in its stats.
+        fn record_deposit(&mut self, resource: ResourceAddress, contents: &LockContents) {
+            let mut stats = self.get_resource_lock_stats(resource);
+            stats.add(contents);
+            self.resource_lock_stats.insert(resource, stats);
+        }
+
+        /// Records an item taken from an existing lock of a resource in its stats.
+        fn record_withdrawal(&mut self, resource: ResourceAddress, contents: &LockContents) {
+            let mut stats = self.get_resource_lock_stats(resource);
+            stats.remove(contents);
+            self.resource_lock_stats.insert(resource, stats);
+        }
+
+        /// Returns the contents of a bucket.
+        fn contents_of(item: &Bucket) -> LockContents {
+            match item.resource_manager().resource_type() {
+                ResourceType::Fungible{..} => LockContents::Fungible(item.amount()),
+                ResourceType::NonFungible{..} => LockContents::NonFungible(item.as_non_fungible().non_fungible_local_ids()),
+            }
+        }
+
+        /// Checks a lock receipt proof and returns the lock receipt.
+        fn check_lock_receipt(&self, lock_receipt_proof: Proof) -> NonFungible<LockReceipt> {
+            let lock_receipt_resource = if lock_receipt_proof.resource_address() == self.soulbound_lock_receipt_manager.address() {
+                self.soulbound_lock_receipt_manager.address()
+            } else {
+                self.lock_receipt_manager.address()
+            };
+            lock_receipt_proof
+                .check_with_message(lock_receipt_resource, "Invalid lock receipt")
+                .as_non_fungible()
+                .non_fungible::<LockReceipt>()
+        }
+
+        /// Asserts that a lock can be unlocked at the given time or through its price condition, combined with its
+        /// unlock condition, or early through its early unlock rule.
+        fn assert_unlockable(lock_receipt_data: &LockReceipt, current_time: Instant) {
+            Self::assert_unlock_rule(lock_receipt_data);
+
+            let options = &lock_receipt_data.options;
+            let unlockable = lock_receipt_data.unlockable_at.is_some_and(|unlockable_at| current_time >= unlockable_at)
+                || options.price_condition.as_ref().is_some_and(|price_condition| price_condition.is_met());
+            let unlockable = match &options.unlock_condition {
+                Some(unlock_condition) => match unlock_condition.mode {
+                    ConditionMode::And => unlockable && unlock_condition.is_met(),
+                    ConditionMode::Or => unlockable || unlock_condition.is_met(),
+                },
+                None => unlockable,
+            };
+            if unlockable {
+                return;
+            }
+
+            match &lock_receipt_data.options.early_unlock_rule {
+                Some(early_unlock_rule) => Runtime::assert_access_rule(early_unlock_rule.clone()),
+                None => panic!("Item can not yet be unlocked, unlockable at: {:?}", lock_receipt_data.unlockable_at),
+            }
+        }
+
+        /// Returns whether a timed vesting lock has not fully vested yet.
+        fn is_vesting(lock_receipt_data: &LockReceipt, current_time: Instant) -> bool {
+            lock_receipt_data.schedule.is_some()
+                && lock_receipt_data.kind == LockKind::Timed
+                && lock_receipt_data.unlockable_at.is_some_and(|unlockable_at| current_time < unlockable_at)
+        }
+
+        /// Asserts that the unlock rule of a lock, if any, is satisfied.
+        fn assert_unlock_rule(lock_receipt_data: &LockReceipt) {
+            if let Some(unlock_rule) = &lock_receipt_data.options.unlock_rule {
+                Runtime::assert_access_rule(unlock_rule.clone());
+            }
+        }
+
+        /// Asserts that a lock is not permanent, as permanent locks can no longer be modified.
+        fn assert_not_permanent(lock_receipt_data: &LockReceipt) {
+            assert!(lock_receipt_data.kind != LockKind::Permanent, "Permanent locks can not be modified");
+        }
+
+        /// Asserts that a lock holds a single resource.
+        fn assert_single_resource(lock_receipt_data: &LockReceipt) {
+            assert!(
+                lock_receipt_data.additional_contents.is_empty(),
+                "Not supported for multi-resource locks"
+            );
+        }
+
+        /// Deposits one or more items into new vaults and mints their lock receipt.
+        fn create_lock(
+            &mut self,
+            items: Vec<Bucket>,
+            unlockable_at: Option<Instant>,
+            schedule: Option<LockSchedule>,
+            display: LockDisplay,
+            options: LockOptions,
+        ) -> Bucket {
+            // Assert only vesting locks are revocable, hash locks have a timeout and do not vest, and AND conditions
+            // have an unlock time
+            assert!(
+                options.revoke_rule.is_none() || schedule.is_some(),
+                "Revocable locks require a vesting schedule"
+            );
+            assert!(
+                options.hash_lock.is_none() || unlockable_at.is_some(),
+                "Hash locks require a timeout"
+            );
+            assert!(
+                options.hash_lock.is_none() || schedule.is_none(),
+                "Hash locks can not vest"
+            );
+            assert!(
+                options.unlock_condition.as_ref().map(|unlock_condition| unlock_condition.mode) != Some(ConditionMode::And)
+                    || unlockable_at.is_some(),
+                "Unlock conditions combined with AND require an unlock time"
+            );
+
+            // Get the current time
+            let current_time = Clock::current_time(TimePrecisionV2::Second);
+
+            // Get the next id and increment the counter
+            let id = NonFungibleLocalId::integer(self.counter);
+            self.counter += 1;
+
+            // Get the kind of lock
+            let kind = LockKind::of(unlockable_at, &options);
+            
+            // Get the resource address and the bucket contents
+            let mut items = items.into_iter();
+            let item = items.next().unwrap();
+            let resource = item.resource_address();
+            let locked_contents = Self::contents_of(&item);
+
+            // Deposit the item into the vault
+            self.vaults.insert(id.clone(), Vault::with_bucket(item));
+
+            // Deposit any additional items into their own vaults
+            let mut additional_contents = IndexMap::new();
+            let mut additional_vaults = IndexMap::new();
+            for item in items {
+                additional_contents.insert(item.resource_address(), Self::contents_of(&item));
+                additional_vaults.insert(item.resource_address(), Vault::with_bucket(item));
+            }
+
+            // Index the lock by each of its resources and record it in their stats
+            self.index_lock(resource, &id);
+            self.record_lock(resource, &locked_contents);
+            for (additional_resource, contents) in additional_contents.iter() {
+                self.index_lock(*additional_resource, &id);
+                self.record_lock(*additional_resource, contents);
+            }
+
+            // Emit new lock event
+            if additional_contents.is_empty() {
+                Runtime::emit_event(EventLock {
+                    lock_id: id.clone(),
+                    kind,
+                    resource,
+                    locked_contents: locked_contents.clone(),
+                    locked_at: current_time,
+                    unlockable_at,
+                    schedule: schedule.clone(),
+                    options: options.clone(),
+                });
+            } else {
+                self.additional_vaults.insert(id.clone(), additional_vaults);
+
+                let mut all_contents = indexmap!(resource => locked_contents.clone());
+                all_contents.extend(additional_contents.clone());
+                Runtime::emit_event(EventLockMany {
+                    lock_id: id.clone(),
+                    kind,
+                    locked_contents: all_contents,
+                    locked_at: current_time,
+                    unlockable_at,
+                    options: options.clone(),
+                });
+            }
+
+            // Mint and return the lock receipt
+            let lock_receipt_manager = if options.soulbound {
+                self.soulbound_lock_receipt_manager
+            } else {
+                self.lock_receipt_manager
+            };
+            lock_receipt_manager.mint_non_fungible(&id, LockReceipt {
+                name: display.name,
+                description: display.description,
+                key_image_url: display.key_image_url,
+                resource,
+                kind,
+                locked_contents,
+                additional_contents,
+                locked_at: current_time,
+                unlockable_at,
+                schedule,
+                options,
+                claimed_amount: Decimal::ZERO,
+                claimed_tranches: 0,
+                unlocked_at: None,
+                revoked_at: None,
+            })
+        }
     }
 }
@@ -0,0 +1,30 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod mock_condition_mod {
+    struct MockCondition {
+        met: bool,
+    }
+
+    impl MockCondition {
+        /// Creates a new mock condition that is not met.
+        pub fn new() -> Global<MockCondition> {
+            Self {
+                met: false,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        /// Sets whether the condition is met.
+        pub fn set_met(&mut self, met: bool) {
+            self.met = met;
+        }
+
+        /// Returns whether the condition is met.
+        pub fn is_met(&self) -> bool {
+            self.met
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod mock_oracle_mod {
+    struct MockOracle {
+        prices: KeyValueStore<ResourceAddress, Decimal>,
+    }
+
+    impl MockOracle {
+        /// Creates a new mock oracle without prices.
+        pub fn new() -> Global<MockOracle> {
+            Self {
+                prices: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        /// Sets the price of a resource.
+        pub fn set_price(&mut self, resource: ResourceAddress, price: Decimal) {
+            self.prices.insert(resource, price);
+        }
+
+        /// Returns the price of a resource, zero if it has no price.
+        pub fn get_price(&self, resource: ResourceAddress) -> Decimal {
+            self.prices.get(&resource)
+                .map(|price| *price)
+                .unwrap_or_default()
+        }
+    }
+}
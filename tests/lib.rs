@@ -1,8 +1,8 @@
 use scrypto_test::prelude::*;
-use scrypto::prelude::Url;
+use scrypto::prelude::{Account, Global, ObjectStubHandle, Url};
 
 use locker::locker_mod_test::*;
-use locker::{LockContents, LockReceipt};
+use locker::{ConditionMode, HashLock, LockContents, LockDisplay, LockFee, LockKind, LockOptions, LockReceipt, LockSchedule, PriceCondition, ResourcePolicy, UnlockCondition, MAX_FEE_BASIS_POINTS};
 
 #[test]
 fn test_lock_fungible() -> Result<(), RuntimeError> {
@@ -14,7 +14,7 @@ fn test_lock_fungible() -> Result<(), RuntimeError> {
     let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(1), &mut env)?
         .resource_address(&mut env)?;
-    let mut locker = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let token = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(100), &mut env)?;
     let resource = token.resource_address(&mut env)?;
@@ -25,9 +25,13 @@ fn test_lock_fungible() -> Result<(), RuntimeError> {
     // Act
     let receipt = locker.lock(
         token, current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
     let receipt_resource = receipt.resource_address(&mut env)?;
@@ -39,6 +43,7 @@ fn test_lock_fungible() -> Result<(), RuntimeError> {
     assert_eq!(receipt_data.description, "Test Lock Description");
     assert_eq!(receipt_data.key_image_url, Url::of("https://example.com/key.png"));
     assert_eq!(receipt_data.resource, resource);
+    assert_eq!(receipt_data.kind, LockKind::Timed);
     assert_eq!(receipt_data.locked_contents, LockContents::Fungible(amount));
     assert_eq!(receipt_data.locked_at, current_time);
     assert_eq!(receipt_data.unlockable_at, current_time.add_seconds(1));
@@ -69,7 +74,7 @@ fn test_lock_non_fungible() -> Result<(), RuntimeError> {
     let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(1), &mut env)?
         .resource_address(&mut env)?;
-    let mut locker = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
         .mint_initial_supply(vec![(), (), ()], &mut env)?;
     let resource = nft.resource_address(&mut env)?;
@@ -80,9 +85,13 @@ fn test_lock_non_fungible() -> Result<(), RuntimeError> {
     // Act
     let receipt = locker.lock(
         nft, current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
     let receipt_resource = receipt.resource_address(&mut env)?;
@@ -124,7 +133,7 @@ fn test_lock_unlock_multiple() -> Result<(), RuntimeError> {
     let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(1), &mut env)?
         .resource_address(&mut env)?;
-    let mut locker = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let token_1 = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(100), &mut env)?;
     let resource_1 = token_1.resource_address(&mut env)?;
@@ -139,16 +148,24 @@ fn test_lock_unlock_multiple() -> Result<(), RuntimeError> {
 
     let receipts = locker.lock(
         token_1, current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
     let receipt_2 = locker.lock(
         token_2, current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
     receipts.put(receipt_2, &mut env)?;
@@ -177,16 +194,20 @@ fn test_lock_unlock_before_never() -> Result<(), RuntimeError> {
     let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(1), &mut env)?
         .resource_address(&mut env)?;
-    let mut locker = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let token = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(100), &mut env)?;
 
     let receipt = locker.lock(
         token, 
         None,
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
 
@@ -214,7 +235,7 @@ fn test_lock_unlock_before_some() -> Result<(), RuntimeError> {
     let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(1), &mut env)?
         .resource_address(&mut env)?;
-    let mut locker = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let token = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(100), &mut env)?;
 
@@ -223,9 +244,13 @@ fn test_lock_unlock_before_some() -> Result<(), RuntimeError> {
     let receipt = locker.lock(
         token, 
         current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
 
@@ -253,10 +278,10 @@ fn test_lock_unlock_wrong_receipt() -> Result<(), RuntimeError> {
     let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(1), &mut env)?
         .resource_address(&mut env)?;
-    let mut locker_1 = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker_1 = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let token_1 = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(100), &mut env)?;
-    let mut locker_2 = Locker::new(owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let mut locker_2 = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
     let token_2 = ResourceBuilder::new_fungible(OwnerRole::None)
         .mint_initial_supply(dec!(100), &mut env)?;
 
@@ -265,17 +290,25 @@ fn test_lock_unlock_wrong_receipt() -> Result<(), RuntimeError> {
     let _receipt = locker_1.lock(
         token_1, 
         current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
     let fake_receipt = locker_2.lock(
         token_2, 
         current_time.add_seconds(1),
-        "Test Lock".to_string(),
-        "Test Lock Description".to_string(),
-        Url::of("https://example.com/key.png"),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
         &mut env
     )?;
 
@@ -294,3 +327,2988 @@ fn test_lock_unlock_wrong_receipt() -> Result<(), RuntimeError> {
 
     Ok(())
 }
+
+#[test]
+fn test_lock_vested_claim() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+
+    let start = env.get_current_time();
+    let schedule = LockSchedule::Linear {
+        start,
+        end: start.add_seconds(100).unwrap(),
+        cliff: start.add_seconds(10),
+    };
+
+    let receipt = locker.lock_vested(
+        token,
+        schedule.clone(),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id.clone(), &mut env)?;
+    assert_eq!(receipt_data.schedule, Some(schedule));
+    assert_eq!(receipt_data.unlockable_at, start.add_seconds(100));
+    assert_eq!(receipt_data.claimed_amount, dec!(0));
+
+    // Act
+    env.set_current_time(start.add_seconds(25).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let claimed = locker.claim_vested(proof, &mut env)?;
+
+    // Assert
+    assert_eq!(claimed.resource_address(&mut env)?, resource);
+    assert_eq!(claimed.amount(&mut env)?, dec!(25));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id.clone(), &mut env)?;
+    assert_eq!(receipt_data.claimed_amount, dec!(25));
+
+    // Act
+    env.set_current_time(start.add_seconds(100).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(75));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_vested_claim_all() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+
+    let start = env.get_current_time();
+    let schedule = LockSchedule::Linear {
+        start,
+        end: start.add_seconds(100).unwrap(),
+        cliff: None,
+    };
+
+    let receipt = locker.lock_vested(
+        token,
+        schedule,
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(100).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let claimed = locker.claim_vested(proof, &mut env)?;
+
+    // Assert
+    assert_eq!(claimed.amount(&mut env)?, dec!(100));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.claimed_amount, dec!(100));
+    assert_eq!(receipt_data.unlocked_at, start.add_seconds(100));
+
+    let stats = locker.get_resource_lock_stats(resource, &mut env)?;
+    assert_eq!(stats.locked_amount, dec!(0));
+    assert_eq!(stats.active_locks, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_vested_claim_before_cliff() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        token,
+        LockSchedule::Linear {
+            start,
+            end: start.add_seconds(100).unwrap(),
+            cliff: start.add_seconds(10),
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    env.set_current_time(start.add_seconds(5).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.claim_vested(proof, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Nothing to claim"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_tranches_claim() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        token,
+        LockSchedule::Tranches {
+            tranches: vec![
+                (start.add_seconds(10).unwrap(), dec!(25)),
+                (start.add_seconds(20).unwrap(), dec!(25)),
+                (start.add_seconds(30).unwrap(), dec!(50)),
+            ],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(25).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let claimed = locker.claim_vested(proof, &mut env)?;
+
+    // Assert
+    assert_eq!(claimed.amount(&mut env)?, dec!(50));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id.clone(), &mut env)?;
+    assert_eq!(receipt_data.unlockable_at, start.add_seconds(30));
+    assert_eq!(receipt_data.claimed_amount, dec!(50));
+    assert_eq!(receipt_data.claimed_tranches, 2);
+
+    // Act
+    env.set_current_time(start.add_seconds(30).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(50));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_tranches_unlock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        token,
+        LockSchedule::Tranches {
+            tranches: vec![
+                (start.add_seconds(10).unwrap(), dec!(25)),
+                (start.add_seconds(20).unwrap(), dec!(25)),
+                (start.add_seconds(30).unwrap(), dec!(50)),
+            ],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(25).unwrap());
+    let mut unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks.len(), 2);
+    let receipt = unlocks.pop().unwrap();
+    assert_eq!(receipt.non_fungible_local_ids(&mut env)?, indexset!(receipt_id.clone()));
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(50));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.claimed_tranches, 2);
+    assert_eq!(receipt_data.unlocked_at, None);
+
+    // Act
+    env.set_current_time(start.add_seconds(30).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks.len(), 1);
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(50));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_tranches_invalid_sum() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let start = env.get_current_time();
+
+    // Act
+    let result = locker.lock_vested(
+        token,
+        LockSchedule::Tranches {
+            tranches: vec![
+                (start.add_seconds(10).unwrap(), dec!(25)),
+                (start.add_seconds(20).unwrap(), dec!(50)),
+            ],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    );
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Vesting tranche percentages must sum to 100"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_batches_claim() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
+        .mint_initial_supply(vec![(), (), ()], &mut env)?;
+    let nft_ids: Vec<NonFungibleLocalId> = nft.non_fungible_local_ids(&mut env)?.into_iter().collect();
+    let first_batch = indexset!(nft_ids[0].clone());
+    let second_batch = indexset!(nft_ids[1].clone(), nft_ids[2].clone());
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        nft,
+        LockSchedule::Batches {
+            batches: vec![
+                (start.add_seconds(10).unwrap(), first_batch.clone()),
+                (start.add_seconds(20).unwrap(), second_batch.clone()),
+            ],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(15).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let claimed = locker.claim_vested(proof, &mut env)?;
+
+    // Assert
+    assert_eq!(claimed.non_fungible_local_ids(&mut env)?, first_batch);
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id.clone(), &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::NonFungible(second_batch.clone()));
+    assert_eq!(receipt_data.claimed_tranches, 1);
+
+    // Act
+    env.set_current_time(start.add_seconds(20).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].non_fungible_local_ids(&mut env)?, second_batch);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_batches_unlock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
+        .mint_initial_supply(vec![(), (), ()], &mut env)?;
+    let nft_ids: Vec<NonFungibleLocalId> = nft.non_fungible_local_ids(&mut env)?.into_iter().collect();
+    let first_batch = indexset!(nft_ids[0].clone());
+    let second_batch = indexset!(nft_ids[1].clone(), nft_ids[2].clone());
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        nft,
+        LockSchedule::Batches {
+            batches: vec![
+                (start.add_seconds(10).unwrap(), first_batch.clone()),
+                (start.add_seconds(20).unwrap(), second_batch.clone()),
+            ],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(15).unwrap());
+    let mut unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks.len(), 2);
+    let receipt = unlocks.pop().unwrap();
+    assert_eq!(unlocks[0].non_fungible_local_ids(&mut env)?, first_batch);
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::NonFungible(second_batch.clone()));
+
+    // Act
+    env.set_current_time(start.add_seconds(20).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks.len(), 1);
+    assert_eq!(unlocks[0].non_fungible_local_ids(&mut env)?, second_batch);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_add_to_vesting_lock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
+        .mint_initial_supply(vec![(), ()], &mut env)?;
+    let nft_ids = nft.non_fungible_local_ids(&mut env)?;
+    let added_nft = nft.take_non_fungibles(indexset!(nft_ids[1].clone()), &mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        nft,
+        LockSchedule::Batches {
+            batches: vec![(start.add_seconds(10).unwrap(), indexset!(nft_ids[0].clone()))],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.add_to_lock(proof, added_nft, None, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Vesting locks can not be added to"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_partial_unlock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let unlocked = locker.partial_unlock(proof, dec!(40), &mut env)?;
+
+    // Assert
+    assert_eq!(unlocked.resource_address(&mut env)?, resource);
+    assert_eq!(unlocked.amount(&mut env)?, dec!(40));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!(60)));
+    assert_eq!(receipt_data.unlocked_at, None);
+
+    // Act
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(60));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_partial_unlock_all() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let unlocked = locker.partial_unlock(proof, dec!(100), &mut env)?;
+
+    // Assert
+    assert_eq!(unlocked.amount(&mut env)?, dec!(100));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!(0)));
+    assert_eq!(receipt_data.unlocked_at, current_time.add_seconds(2));
+
+    let stats = locker.get_resource_lock_stats(resource, &mut env)?;
+    assert_eq!(stats.locked_amount, dec!(0));
+    assert_eq!(stats.active_locks, 0);
+
+    // Act
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Lock has already been unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_partial_unlock_before() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.partial_unlock(proof, dec!(40), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains(format!("Item can not yet be unlocked, unlockable at: {:?}", current_time.add_seconds(1)).as_str()), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_extend() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    locker.extend_lock(proof, current_time.add_seconds(10), &mut env)?;
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.unlockable_at, current_time.add_seconds(10));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains(format!("Item can not yet be unlocked, unlockable at: {:?}", current_time.add_seconds(10)).as_str()), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_extend_earlier() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.extend_lock(proof, current_time.add_seconds(5), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains(format!("Lock can only be extended, unlockable at: {:?}", current_time.add_seconds(10)).as_str()), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_add_to_lock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
+        .mint_initial_supply(vec![(), (), ()], &mut env)?;
+    let nft_ids = nft.non_fungible_local_ids(&mut env)?;
+    let added_nft = nft.take_non_fungibles(indexset!(nft_ids[2].clone()), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        nft, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    locker.add_to_lock(proof, added_nft, None, &mut env)?;
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::NonFungible(nft_ids.clone()));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].non_fungible_local_ids(&mut env)?, nft_ids);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_add_to_lock_wrong_resource() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token_1 = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource_1 = token_1.resource_address(&mut env)?;
+    let token_2 = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token_1, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.add_to_lock(proof, token_2, None, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains(format!("Invalid item, locked resource: {:?}", resource_1).as_str()), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_add_to_lock_after_unlock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let token_2 = token.take(dec!(40), &mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let beneficiary = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { beneficiary: Some(beneficiary), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    locker.unlock_to_beneficiary(receipt_id, &mut env)?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.add_to_lock(proof, token_2, None, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Lock has already been unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_split() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+
+    // Act
+    let receipts = locker.split(
+        receipt,
+        vec![LockContents::Fungible(dec!(30)), LockContents::Fungible(dec!(20))],
+        &mut env
+    )?;
+    let receipt_ids = receipts.non_fungible_local_ids(&mut env)?;
+
+    // Assert
+    assert_eq!(receipt_ids.len(), 3);
+    for (receipt_id, amount) in receipt_ids.iter().zip([dec!(30), dec!(20), dec!(50)]) {
+        let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id.clone(), &mut env)?;
+        assert_eq!(receipt_data.locked_contents, LockContents::Fungible(amount));
+        assert_eq!(receipt_data.unlockable_at, current_time.add_seconds(1));
+    }
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let unlocks = locker.unlock(receipts, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(30));
+    assert_eq!(unlocks[1].amount(&mut env)?, dec!(20));
+    assert_eq!(unlocks[2].amount(&mut env)?, dec!(50));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_split_too_much() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let result = locker.split(
+        receipt,
+        vec![LockContents::Fungible(dec!(60)), LockContents::Fungible(dec!(60))],
+        &mut env
+    );
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Invalid split"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_merge() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let token_2 = token.take(dec!(40), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipts = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_2 = locker.lock(
+        token_2, current_time.add_seconds(5),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    receipts.put(receipt_2, &mut env)?;
+    let receipt_resource = receipts.resource_address(&mut env)?;
+
+    // Act
+    let receipt = locker.merge(receipts, &mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.resource, resource);
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!(100)));
+    assert_eq!(receipt_data.unlockable_at, current_time.add_seconds(5));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(5).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(100));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_merge_different_resources() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token_1 = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let token_2 = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipts = locker.lock(
+        token_1, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_2 = locker.lock(
+        token_2, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    receipts.put(receipt_2, &mut env)?;
+
+    // Act
+    let result = locker.merge(receipts, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Merged locks must be of the same resource"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_many() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let token_resource = token.resource_address(&mut env)?;
+    let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
+        .mint_initial_supply(vec![(), ()], &mut env)?;
+    let nft_resource = nft.resource_address(&mut env)?;
+    let nft_ids = nft.non_fungible_local_ids(&mut env)?;
+
+    let current_time = env.get_current_time();
+
+    // Act
+    let receipt = locker.lock_many(
+        vec![token, nft],
+        current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.resource, token_resource);
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!(100)));
+    assert_eq!(receipt_data.additional_contents, indexmap!(nft_resource => LockContents::NonFungible(nft_ids.clone())));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks.len(), 2);
+    assert_eq!(unlocks[0].resource_address(&mut env)?, token_resource);
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(100));
+    assert_eq!(unlocks[1].resource_address(&mut env)?, nft_resource);
+    assert_eq!(unlocks[1].non_fungible_local_ids(&mut env)?, nft_ids);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_unlock_to_beneficiary() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let beneficiary = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { beneficiary: Some(beneficiary), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    locker.unlock_to_beneficiary(receipt_id.clone(), &mut env)?;
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.unlocked_at, current_time.add_seconds(2));
+
+    // Act
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Lock has already been unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_early_unlock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let amount = token.amount(&mut env)?;
+    let badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let badge_resource = badge.resource_address(&mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(100),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { early_unlock_rule: Some(rule!(require(badge_resource))), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.options.early_unlock_rule, Some(rule!(require(badge_resource))));
+
+    // Act
+    LocalAuthZone::push(badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, amount);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_early_unlock_without_badge() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let badge_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(100),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { early_unlock_rule: Some(rule!(require(badge_resource))), ..Default::default() },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    assert!(matches!(
+        result.err(),
+        Some(RuntimeError::SystemError(SystemError::AssertAccessRuleFailed))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_permanent() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let added_token = token.take(dec!(10), &mut env)?;
+
+    // Act
+    let receipt = locker.lock(
+        token, 
+        None,
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.kind, LockKind::Permanent);
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.add_to_lock(proof, added_token, None, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Permanent locks can not be modified"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_queries() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let token_2 = token.take(dec!(40), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt_1 = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_id_1 = receipt_1.non_fungible_local_ids(&mut env)?[0].clone();
+    let receipt_2 = locker.lock(
+        token_2, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_id_2 = receipt_2.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    let lock = locker.get_lock(receipt_id_1.clone(), &mut env)?;
+    let lock_ids = locker.get_locks_by_resource(resource, 0, 10, &mut env)?;
+    let lock_ids_page = locker.get_locks_by_resource(resource, 1, 10, &mut env)?;
+    let lock_count = locker.get_lock_count_by_resource(resource, &mut env)?;
+    let total_locked = locker.total_locked(resource, &mut env)?;
+
+    // Assert
+    assert_eq!(lock.lock_id, receipt_id_1);
+    assert_eq!(lock.lock_receipt.resource, resource);
+    assert_eq!(lock.lock_receipt.unlockable_at, current_time.add_seconds(1));
+    assert_eq!(lock.balance, dec!(60));
+    assert_eq!(lock_ids, vec![receipt_id_1, receipt_id_2.clone()]);
+    assert_eq!(lock_ids_page, vec![receipt_id_2]);
+    assert_eq!(lock_count, 2);
+    assert_eq!(total_locked, dec!(100));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let _unlocks = locker.unlock(receipt_1, &mut env)?;
+    let total_locked = locker.total_locked(resource, &mut env)?;
+
+    // Assert
+    assert_eq!(total_locked, dec!(40));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_resource_stats() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let token_2 = token.take(dec!(40), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt_1 = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let receipt_2 = locker.lock(
+        token_2, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let stats = locker.get_resource_lock_stats(resource, &mut env)?;
+
+    // Assert
+    assert_eq!(stats.locked_amount, dec!(100));
+    assert_eq!(stats.locked_non_fungibles, 0);
+    assert_eq!(stats.active_locks, 2);
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let _unlocks = locker.unlock(receipt_1, &mut env)?;
+    let stats = locker.get_resource_lock_stats(resource, &mut env)?;
+    let metadata = locker.get_resource_lock_stats_metadata(resource, &mut env)?;
+
+    // Assert
+    assert_eq!(stats.locked_amount, dec!(40));
+    assert_eq!(stats.active_locks, 1);
+    assert_eq!(metadata.get("locked_amount"), Some(&"40".to_string()));
+    assert_eq!(metadata.get("active_locks"), Some(&"1".to_string()));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(11).unwrap());
+    let _unlocks = locker.unlock(receipt_2, &mut env)?;
+    let stats = locker.get_resource_lock_stats(resource, &mut env)?;
+
+    // Assert
+    assert_eq!(stats.locked_amount, dec!(0));
+    assert_eq!(stats.active_locks, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_flat_fee() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let fee_token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(10), &mut env)?;
+    let fee_resource = fee_token.resource_address(&mut env)?;
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let fee = LockFee::Flat { resource: fee_resource, amount: dec!(5) };
+    locker.set_fee(Some(fee.clone()), &mut env)?;
+    assert_eq!(locker.get_fee(&mut env)?, Some(fee));
+
+    let current_time = env.get_current_time();
+
+    // Act
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        Some(fee_token.take(dec!(5), &mut env)?),
+        &mut env
+    )?;
+    let fees = locker.withdraw_fees(fee_resource, &mut env)?;
+
+    // Assert
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!(100)));
+    assert_eq!(fees.amount(&mut env)?, dec!(5));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_basis_points_fee() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    locker.set_fee(Some(LockFee::BasisPoints(250)), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    // Act
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+    let fees = locker.withdraw_fees(resource, &mut env)?;
+
+    // Assert
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!("97.5")));
+    assert_eq!(fees.amount(&mut env)?, dec!("2.5"));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_add_to_lock_basis_points_fee() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let token_2 = token.take(dec!(60), &mut env)?;
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    locker.set_fee(Some(LockFee::BasisPoints(250)), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    locker.add_to_lock(proof, token_2, None, &mut env)?;
+    let fees = locker.withdraw_fees(resource, &mut env)?;
+
+    // Assert
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!("97.5")));
+    assert_eq!(fees.amount(&mut env)?, dec!("2.5"));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_fee_above_max_basis_points() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+
+    // Act
+    let result = locker.set_fee(Some(LockFee::BasisPoints(MAX_FEE_BASIS_POINTS + 1)), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Invalid fee"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_resource_policy() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let token_2 = token.take(dec!(50), &mut env)?;
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    locker.set_resource_policy(ResourcePolicy::Allowlist(indexset!()), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    // Act
+    locker.set_resource_listed(resource, true, &mut env)?;
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Assert
+    assert_eq!(locker.get_resource_policy(&mut env)?, ResourcePolicy::Allowlist(indexset!(resource)));
+    assert_eq!(receipt.amount(&mut env)?, dec!(1));
+
+    // Act
+    locker.set_resource_listed(resource, false, &mut env)?;
+    let result = locker.lock(
+        token_2, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    );
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Resource not allowed"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_paused() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let token_2 = token.take(dec!(50), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    locker.pause(&mut env)?;
+    assert!(locker.is_paused(&mut env)?);
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(50));
+
+    // Act
+    let result = locker.lock(
+        token_2, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    );
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Locker is paused"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_set_fee_without_fee_manager() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let fee_manager_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let fee_manager_resource = fee_manager_badge.resource_address(&mut env)?;
+    let pauser_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let pauser_resource = pauser_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, fee_manager_resource, pauser_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Act
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(pauser_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.set_fee(Some(LockFee::BasisPoints(100)), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_)))) => {},
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_pause_without_pauser() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let fee_manager_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let fee_manager_resource = fee_manager_badge.resource_address(&mut env)?;
+    let pauser_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let pauser_resource = pauser_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, fee_manager_resource, pauser_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Act
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(fee_manager_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.pause(&mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_)))) => {},
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_set_resource_policy_without_owner() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let fee_manager_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let fee_manager_resource = fee_manager_badge.resource_address(&mut env)?;
+    let pauser_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let pauser_resource = pauser_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, fee_manager_resource, pauser_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Act
+    LocalAuthZone::push(fee_manager_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(pauser_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.set_resource_policy(ResourcePolicy::Denylist(indexset!()), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_)))) => {},
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_withdraw_fees_without_fee_manager() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let fee_manager_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let fee_manager_resource = fee_manager_badge.resource_address(&mut env)?;
+    let pauser_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let pauser_resource = pauser_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, fee_manager_resource, pauser_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Act
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(pauser_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.withdraw_fees(XRD, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_)))) => {},
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_unpause_without_pauser() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let fee_manager_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let fee_manager_resource = fee_manager_badge.resource_address(&mut env)?;
+    let pauser_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let pauser_resource = pauser_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, fee_manager_resource, pauser_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Act
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(fee_manager_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.unpause(&mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_)))) => {},
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_set_resource_listed_without_owner() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let owner_resource = owner_badge.resource_address(&mut env)?;
+    let fee_manager_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let fee_manager_resource = fee_manager_badge.resource_address(&mut env)?;
+    let pauser_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let pauser_resource = pauser_badge.resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, fee_manager_resource, pauser_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Act
+    LocalAuthZone::push(fee_manager_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(pauser_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.set_resource_listed(XRD, true, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_)))) => {},
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_new_with_owner_badge() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    // Act
+    let (mut locker, owner_badge) = Locker::new_with_owner_badge(Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+
+    // Assert
+    assert_eq!(owner_badge.amount(&mut env)?, dec!(1));
+
+    LocalAuthZone::push(owner_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    locker.pause(&mut env)?;
+    assert!(locker.is_paused(&mut env)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_soulbound() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let token_2 = token.take(dec!(40), &mut env)?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions::default(),
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let soulbound_receipt = locker.lock(
+        token_2, current_time.add_seconds(1),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { soulbound: true, ..Default::default() },
+        None,
+        &mut env
+    )?;
+
+    // Assert
+    let soulbound_receipt_resource = soulbound_receipt.resource_address(&mut env)?;
+    let soulbound_receipt_id = soulbound_receipt.non_fungible_local_ids(&mut env)?[0].clone();
+    assert_ne!(soulbound_receipt_resource, receipt.resource_address(&mut env)?);
+
+    let receipt_data: LockReceipt = ResourceManager(soulbound_receipt_resource).get_non_fungible_data(soulbound_receipt_id.clone(), &mut env)?;
+    assert!(receipt_data.options.soulbound);
+
+    // Act
+    env.set_current_time(current_time.add_seconds(2).unwrap());
+    let proof = soulbound_receipt.create_proof_of_all(&mut env)?;
+    let unlocks = locker.unlock_with_proof(proof, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(40));
+
+    let receipt_data: LockReceipt = ResourceManager(soulbound_receipt_resource).get_non_fungible_data(soulbound_receipt_id, &mut env)?;
+    assert_eq!(receipt_data.unlocked_at, current_time.add_seconds(2));
+
+    // Act
+    let account_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let account_badge_resource = account_badge.resource_address(&mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::Fixed(rule!(require(account_badge_resource))), address_reservation: None },
+    )?;
+    LocalAuthZone::push(account_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    env.call_method_typed::<_, _, ()>(account.0, ACCOUNT_DEPOSIT_IDENT, &AccountDepositInput { bucket: soulbound_receipt })?;
+    let result = env.call_method_typed::<_, _, Bucket>(
+        account.0,
+        ACCOUNT_WITHDRAW_IDENT,
+        &AccountWithdrawInput { resource_address: soulbound_receipt_resource, amount: dec!(1) },
+    );
+
+    // Assert
+    assert!(matches!(
+        result.err(),
+        Some(RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(_))))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_revoke() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let grantor_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let grantor_resource = grantor_badge.resource_address(&mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        token,
+        LockSchedule::Linear {
+            start,
+            end: start.add_seconds(100).unwrap(),
+            cliff: None,
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { revoke_rule: Some(rule!(require(grantor_resource))), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(25).unwrap());
+    LocalAuthZone::push(grantor_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let revoked = locker.revoke(receipt_id.clone(), &mut env)?;
+
+    // Assert
+    assert_eq!(revoked.amount(&mut env)?, dec!(75));
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::Fungible(dec!(25)));
+    assert_eq!(receipt_data.unlockable_at, start.add_seconds(25));
+    assert_eq!(receipt_data.revoked_at, start.add_seconds(25));
+
+    // Act
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, dec!(25));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_revoke_batches() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let nft = ResourceBuilder::new_ruid_non_fungible(OwnerRole::None)
+        .mint_initial_supply(vec![(), (), ()], &mut env)?;
+    let nft_ids: Vec<NonFungibleLocalId> = nft.non_fungible_local_ids(&mut env)?.into_iter().collect();
+    let first_batch = indexset!(nft_ids[0].clone());
+    let second_batch = indexset!(nft_ids[1].clone(), nft_ids[2].clone());
+    let grantor_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let grantor_resource = grantor_badge.resource_address(&mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        nft,
+        LockSchedule::Batches {
+            batches: vec![
+                (start.add_seconds(10).unwrap(), first_batch.clone()),
+                (start.add_seconds(20).unwrap(), second_batch.clone()),
+            ],
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { revoke_rule: Some(rule!(require(grantor_resource))), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(15).unwrap());
+    LocalAuthZone::push(grantor_badge.create_proof_of_all(&mut env)?, &mut env)?;
+    let revoked = locker.revoke(receipt_id.clone(), &mut env)?;
+
+    // Assert
+    assert_eq!(revoked.non_fungible_local_ids(&mut env)?, second_batch);
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.locked_contents, LockContents::NonFungible(first_batch.clone()));
+    assert_eq!(receipt_data.revoked_at, start.add_seconds(15));
+
+    // Act
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].non_fungible_local_ids(&mut env)?, first_batch);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_revoke_without_badge() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let grantor_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+
+    let start = env.get_current_time();
+    let receipt = locker.lock_vested(
+        token,
+        LockSchedule::Linear {
+            start,
+            end: start.add_seconds(100).unwrap(),
+            cliff: None,
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { revoke_rule: Some(rule!(require(grantor_resource))), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(start.add_seconds(25).unwrap());
+    let result = locker.revoke(receipt_id, &mut env);
+
+    // Assert
+    assert!(matches!(
+        result.err(),
+        Some(RuntimeError::SystemError(SystemError::AssertAccessRuleFailed))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_claim_with_preimage() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let recipient = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+    let preimage = b"secret".to_vec();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(&preimage), recipient }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(current_time.add_seconds(5).unwrap());
+    locker.claim_with_preimage(receipt_id.clone(), preimage, &mut env)?;
+
+    // Assert
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.kind, LockKind::Conditional);
+    assert_eq!(receipt_data.unlocked_at, current_time.add_seconds(5));
+    let received = env.with_auth_module_disabled(|env| {
+        env.call_method_typed::<_, _, Bucket>(
+            account.0,
+            ACCOUNT_WITHDRAW_IDENT,
+            &AccountWithdrawInput { resource_address: resource, amount: dec!(100) },
+        )
+    })?;
+    assert_eq!(received.amount(&mut env)?, dec!(100));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(10).unwrap());
+    let result = locker.refund(receipt, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Lock has already been unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_claim_with_invalid_preimage() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let recipient = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(b"secret"), recipient }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    let result = locker.claim_with_preimage(receipt_id, b"guess".to_vec(), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Invalid preimage"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_refund() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let amount = token.amount(&mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let recipient = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(b"secret"), recipient }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    // Act
+    env.set_current_time(current_time.add_seconds(10).unwrap());
+    let refunds = locker.refund(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(refunds[0].amount(&mut env)?, amount);
+
+    // Act
+    let result = locker.claim_with_preimage(receipt_id, b"secret".to_vec(), &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Lock has already been unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_extend_hash_lock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let recipient = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(b"secret"), recipient }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.extend_lock(proof, None, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Hash locks can not be extended"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_merge_hash_locks() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let recipient = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let current_time = env.get_current_time();
+
+    let receipts = locker.lock(
+        token.take(dec!(50), &mut env)?, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(b"secret"), recipient }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_2 = locker.lock(
+        token, current_time.add_seconds(1000),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(b"secret"), recipient }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    receipts.put(receipt_2, &mut env)?;
+
+    // Act
+    let result = locker.merge(receipts, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Hash locks can not be merged"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_vested_hash_lock() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let account = env.call_function_typed::<_, AccountCreateAdvancedOutput>(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput { owner_role: OwnerRole::None, address_reservation: None },
+    )?;
+    let recipient = Global::<Account>(Account { handle: ObjectStubHandle::Global(account.0.into()) });
+
+    let start = env.get_current_time();
+
+    // Act
+    let result = locker.lock_vested(
+        token,
+        LockSchedule::Linear {
+            start,
+            end: start.add_seconds(100).unwrap(),
+            cliff: None,
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { hash_lock: Some(HashLock { hash: HashLock::hash_of(b"secret"), recipient }), ..Default::default() },
+        None,
+        &mut env
+    );
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Hash locks can not vest"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_price_condition() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+    let oracle_package_address = 
+        PackageFactory::compile_and_publish(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/mock_oracle"), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let oracle: ComponentAddress = env.call_function_typed(oracle_package_address, "MockOracle", "new", &())?;
+    env.call_method_typed::<_, _, ()>(oracle, "set_price", &(resource, dec!("2.5")))?;
+
+    let receipt = locker.lock(
+        token, None,
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { price_condition: Some(PriceCondition { oracle, resource, threshold: dec!(2) }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    assert_eq!(result?[0].amount(&mut env)?, dec!(100));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_price_condition_not_met() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+    let oracle_package_address = 
+        PackageFactory::compile_and_publish(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/mock_oracle"), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let resource = token.resource_address(&mut env)?;
+    let oracle: ComponentAddress = env.call_function_typed(oracle_package_address, "MockOracle", "new", &())?;
+    env.call_method_typed::<_, _, ()>(oracle, "set_price", &(resource, dec!("1.5")))?;
+
+    let receipt = locker.lock(
+        token, None,
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { price_condition: Some(PriceCondition { oracle, resource, threshold: dec!(2) }), ..Default::default() },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Item can not yet be unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_unlock_condition_or() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+    let condition_package_address = 
+        PackageFactory::compile_and_publish(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/mock_condition"), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let condition: ComponentAddress = env.call_function_typed(condition_package_address, "MockCondition", "new", &())?;
+    env.call_method_typed::<_, _, ()>(condition, "set_met", &(true,))?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions {
+            unlock_condition: Some(UnlockCondition { component: condition, method: "is_met".to_string(), mode: ConditionMode::Or }),
+            ..Default::default()
+        },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    env.set_current_time(current_time.add_seconds(5).unwrap());
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    assert_eq!(result?[0].amount(&mut env)?, dec!(100));
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_unlock_condition_and() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+    let condition_package_address = 
+        PackageFactory::compile_and_publish(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/mock_condition"), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let condition: ComponentAddress = env.call_function_typed(condition_package_address, "MockCondition", "new", &())?;
+    env.call_method_typed::<_, _, ()>(condition, "set_met", &(false,))?;
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions {
+            unlock_condition: Some(UnlockCondition { component: condition, method: "is_met".to_string(), mode: ConditionMode::And }),
+            ..Default::default()
+        },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    env.set_current_time(current_time.add_seconds(20).unwrap());
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Item can not yet be unlocked"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_vested_unlock_condition_and() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+    let condition_package_address = 
+        PackageFactory::compile_and_publish(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/mock_condition"), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let condition: ComponentAddress = env.call_function_typed(condition_package_address, "MockCondition", "new", &())?;
+    env.call_method_typed::<_, _, ()>(condition, "set_met", &(false,))?;
+
+    let start = env.get_current_time();
+
+    let receipt = locker.lock_vested(
+        token,
+        LockSchedule::Linear {
+            start,
+            end: start.add_seconds(100).unwrap(),
+            cliff: None,
+        },
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions {
+            unlock_condition: Some(UnlockCondition { component: condition, method: "is_met".to_string(), mode: ConditionMode::And }),
+            ..Default::default()
+        },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    env.set_current_time(start.add_seconds(50).unwrap());
+    let proof = receipt.create_proof_of_all(&mut env)?;
+    let result = locker.claim_vested(proof, &mut env);
+
+    // Assert
+    match result.err() {
+        Some(RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg))) => {
+            assert!(msg.contains("Unlock condition is not met"), "{}", msg);
+        },
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_unlock_rule() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let amount = token.amount(&mut env)?;
+    let badge_a = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let badge_b = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let badge_c_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let unlock_rule = rule!(require_n_of(2, vec![
+        badge_a.resource_address(&mut env)?,
+        badge_b.resource_address(&mut env)?,
+        badge_c_resource,
+    ]));
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { unlock_rule: Some(unlock_rule.clone()), ..Default::default() },
+        None,
+        &mut env
+    )?;
+    let receipt_resource = receipt.resource_address(&mut env)?;
+    let receipt_id = receipt.non_fungible_local_ids(&mut env)?[0].clone();
+
+    let receipt_data: LockReceipt = ResourceManager(receipt_resource).get_non_fungible_data(receipt_id, &mut env)?;
+    assert_eq!(receipt_data.options.unlock_rule, Some(unlock_rule));
+
+    // Act
+    env.set_current_time(current_time.add_seconds(20).unwrap());
+    LocalAuthZone::push(badge_a.create_proof_of_all(&mut env)?, &mut env)?;
+    LocalAuthZone::push(badge_b.create_proof_of_all(&mut env)?, &mut env)?;
+    let unlocks = locker.unlock(receipt, &mut env)?;
+
+    // Assert
+    assert_eq!(unlocks[0].amount(&mut env)?, amount);
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_unlock_rule_below_threshold() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address = 
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let owner_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let mut locker = Locker::new(owner_resource, owner_resource, owner_resource, Url::of("https://example.com"), Url::of("https://example.com/icon.png"), package_address, &mut env)?;
+    let token = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(100), &mut env)?;
+    let badge_a = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?;
+    let badge_b_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let badge_c_resource = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(1), &mut env)?
+        .resource_address(&mut env)?;
+    let unlock_rule = rule!(require_n_of(2, vec![
+        badge_a.resource_address(&mut env)?,
+        badge_b_resource,
+        badge_c_resource,
+    ]));
+
+    let current_time = env.get_current_time();
+
+    let receipt = locker.lock(
+        token, current_time.add_seconds(10),
+        LockDisplay {
+            name: "Test Lock".to_string(),
+            description: "Test Lock Description".to_string(),
+            key_image_url: Url::of("https://example.com/key.png"),
+        },
+        LockOptions { unlock_rule: Some(unlock_rule), ..Default::default() },
+        None,
+        &mut env
+    )?;
+
+    // Act
+    env.set_current_time(current_time.add_seconds(20).unwrap());
+    LocalAuthZone::push(badge_a.create_proof_of_all(&mut env)?, &mut env)?;
+    let result = locker.unlock(receipt, &mut env);
+
+    // Assert
+    assert!(matches!(
+        result.err(),
+        Some(RuntimeError::SystemError(SystemError::AssertAccessRuleFailed))
+    ));
+
+    Ok(())
+}